@@ -1,7 +1,9 @@
 import {
+  parseAlerts,
   parseWaterQuality,
   parseRealtime,
   parseForecast,
+  parseSessions,
 } from "./parsers/index";
 import { forecastFailed } from "./fallback";
 import { setTimeoutError, nonNull } from "./utilities";
@@ -32,6 +34,9 @@ const observerCallback = async (mutationList) => {
       if (mutation.target.id === "water-quality-data") {
         parseWaterQuality(JSON.parse(mutation.target.innerText));
       }
+      if (mutation.target.id === "sessions-data") {
+        parseSessions(JSON.parse(mutation.target.innerText));
+      }
       for (let i = 0; i < mutation.addedNodes.length; i++) {
         if (mutation.addedNodes[i].id === "forecast-complete") {
           parseForecastData();
@@ -47,7 +52,9 @@ const observerCallback = async (mutationList) => {
 function parseForecastData() {
   const forecastData = document.getElementById("forecast-data");
   try {
-    parseForecast(JSON.parse(forecastData.innerText));
+    const data = JSON.parse(forecastData.innerText);
+    parseForecast(data);
+    parseAlerts(data.alerts);
   } catch (e) {
     forecastFailed(e);
   }
@@ -0,0 +1,45 @@
+import { nonNull, removeHidden } from "../utilities";
+
+/**
+ * @typedef {Object} Alert
+ * @property {string} event - e.g. "Small Craft Advisory".
+ * @property {?string} headline
+ * @property {string} severity - "Extreme", "Severe", "Moderate", "Minor" or "Unknown".
+ * @property {string} description
+ * @property {?string} instruction
+ * @property {?string} onset
+ * @property {?string} ends
+ */
+
+/**
+ * Lists the active alerts in the banner, leaving it hidden when there are none
+ *
+ * @param {Alert[]} alerts
+ */
+export function parseAlerts(alerts) {
+  if (!alerts?.length) {
+    return;
+  }
+
+  const list = nonNull(document.getElementById("alerts-list"));
+  list.replaceChildren(
+    ...alerts.map((alert) => {
+      const item = document.createElement("li");
+      const event = document.createElement("span");
+      event.className = "font-semibold text-yellow-400";
+      event.textContent = alert.event;
+      item.append(event);
+
+      if (alert.headline) {
+        item.append(` ${alert.headline}`);
+      }
+      if (alert.instruction) {
+        item.title = alert.instruction;
+      }
+
+      return item;
+    }),
+  );
+
+  removeHidden("alerts-banner");
+}
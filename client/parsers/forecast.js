@@ -22,6 +22,7 @@ const QUALITY_MAP = {
 let qualities;
 let wave_height_labels;
 let wave_heights;
+let glcfs_wave_heights;
 let wind_speeds;
 let wind_directions;
 let wind_gusts;
@@ -34,23 +35,40 @@ let probability_of_thunder;
 
 /**
  * @typedef {Object} ForecastData
- * @property {string[]} wave_height
+ * @property {number[]} wave_height
  * @property {string} current_wave_height
- * @property {string} current_wave_direction
- * @property {string} current_wave_period
- * @property {string[]} wind_speed
- * @property {string[]} wind_direction
- * @property {string[]} wind_gust
- * @property {string[]} wave_period
- * @property {string[]} wave_height_labels
- * @property {string[]} as_of
- * @property {string[]} temperature
- * @property {string[]} probability_of_precipitation
- * @property {string[]} dewpoint
- * @property {string[]} cloud_cover
- * @property {string[]} probability_of_thunder
+ * @property {?number} current_wave_direction
+ * @property {?number} current_wave_period
+ * @property {number[]} wind_speed
+ * @property {number[]} wind_direction
+ * @property {(?number)[]} wind_gust
+ * @property {?string[]} wind_speed_description - e.g. "Gentle breeze" when the wind is in Beaufort forces.
+ * @property {?(?string)[]} wind_gust_description
+ * @property {(?number)[]} wave_period - null for hours the NWS has no value.
+ * @property {string[]} wave_height_labels - e.g. "Fri 09 AM", in the spot's time zone.
+ * @property {string[]} times - The RFC 3339 hour of each value.
+ * @property {?string} as_of - null when the provider doesn't report its model run.
+ * @property {(?number)[]} temperature
+ * @property {(?number)[]} probability_of_precipitation
+ * @property {(?number)[]} dewpoint
+ * @property {(?number)[]} cloud_cover
+ * @property {(?number)[]} probability_of_thunder
  * @property {string[]} quality
+ * @property {import("./realtime.js").QualityReason[]} quality_reasons
+ * @property {import("./realtime.js").Score[]} scores
  * @property {string} starting_at
+ * @property {?{zone: string, updated: string, periods: {name: string, forecast: string}[]}} marine_forecast
+ * @property {import("./alerts.js").Alert[]} alerts
+ * @property {AlternateForecast[]} alternates - Waves from other models for the same hours.
+ * @property {import("./realtime.js").UnitLabels} units - The units the values are in.
+ */
+
+/**
+ * @typedef {Object} AlternateForecast
+ * @property {string} source - e.g. "GLCFS".
+ * @property {(?number)[]} wave_height
+ * @property {(?number)[]} wave_period
+ * @property {(?number)[]} wave_direction
  */
 
 /**
@@ -59,6 +77,10 @@ let probability_of_thunder;
  * @param {ForecastData} data
  */
 export function parseForecast(data) {
+  const glcfs = data.alternates?.find(
+    (alternate) => alternate.source === "GLCFS",
+  );
+
   // prefillLength represents the time in hours that the data from the forecast starts at, e.g.
   // if the forecast data starts at 2 PM, prefillLength = 14;
   const prefillLength = new Date(data.starting_at).getHours();
@@ -77,6 +99,7 @@ export function parseForecast(data) {
     wave_height_labels = data.wave_height_labels.slice(offset, dayAlign);
     qualities = data.quality.slice(offset, dayAlign);
     wave_heights = data.wave_height.slice(offset, dayAlign);
+    glcfs_wave_heights = glcfs?.wave_height.slice(offset, dayAlign);
     wind_speeds = data.wind_speed.slice(offset, dayAlign);
     wind_directions = data.wind_direction.slice(offset, dayAlign);
     wind_gusts = data.wind_gust.slice(offset, dayAlign);
@@ -133,6 +156,12 @@ export function parseForecast(data) {
       .fill(0)
       .concat(data.wave_height)
       .slice(0, dayAlign);
+    glcfs_wave_heights = glcfs
+      ? new Array(prefillLength)
+          .fill(null)
+          .concat(glcfs.wave_height)
+          .slice(0, dayAlign)
+      : undefined;
     wind_speeds = new Array(prefillLength)
       .fill(0)
       .concat(data.wind_speed)
@@ -190,16 +219,21 @@ export function parseForecast(data) {
       removeElements(".wave-quality-loader");
     }
 
-    setStyleAttribute(
-      "wave-icon",
-      `transform: rotate(${data.current_wave_direction}deg);`,
-    );
+    if (data.current_wave_direction != null) {
+      setStyleAttribute(
+        "wave-icon",
+        `transform: rotate(${data.current_wave_direction}deg);`,
+      );
+    }
     removeElements(".wavey");
   }
 
   // The period can be undefined separate from the wave height
   const wave_period_container = document.getElementById("current-wave-period");
-  if (wave_period_container?.innerText === "") {
+  if (
+    wave_period_container?.innerText === "" &&
+    data.current_wave_period != null
+  ) {
     wave_period_container.innerText = data.current_wave_period;
     removeElement("wavey-period-loader");
   }
@@ -208,6 +242,7 @@ export function parseForecast(data) {
   setText("legend-label", wave_height_labels[startingAt]);
   setText("legend-quality", QUALITY_MAP[qualities[startingAt]]);
   setText("legend-wave-height", wave_heights[startingAt]);
+  setGlcfsWaveHeight(startingAt);
   setText("legend-wind-speed", wind_speeds[startingAt]);
   setStyleAttribute(
     "legend-wind-icon",
@@ -215,12 +250,17 @@ export function parseForecast(data) {
   );
   setText("legend-wave-period", wave_period[startingAt]);
   setText("legend-wind-gust", wind_gusts[startingAt]);
-  setText("forecast-as-of", `Updated ${data.as_of}`);
-  setText("forecast-as-of-2", `Updated ${data.as_of}`);
+  if (data.as_of === null) {
+    removeElement("forecast-as-of-container");
+    removeElement("forecast-as-of-container-2");
+  } else {
+    setText("forecast-as-of", `Updated ${data.as_of}`);
+    setText("forecast-as-of-2", `Updated ${data.as_of}`);
 
-  let oneDayMs = 60 * 60 * 24 * 1_000;
-  if (new Date(data.as_of) < new Date() - oneDayMs) {
-    outOfDate(["forecast-as-of-container-2", "forecast-as-of-container"]);
+    let oneDayMs = 60 * 60 * 24 * 1_000;
+    if (new Date(data.as_of) < new Date() - oneDayMs) {
+      outOfDate(["forecast-as-of-container-2", "forecast-as-of-container"]);
+    }
   }
 
   removeElements(".forecast-loader");
@@ -372,6 +412,7 @@ export function parseForecast(data) {
     setText("legend-label", wave_height_labels[x]);
     setText("legend-quality", QUALITY_MAP[color]);
     setText("legend-wave-height", wave_heights[x]);
+    setGlcfsWaveHeight(x);
     setText("legend-wind-speed", wind_speeds[x]);
     setStyleAttribute(
       "legend-wind-icon",
@@ -488,12 +529,11 @@ export function parseForecast(data) {
     },
   });
 
-  // If a wave height is greater than 10, make sure the chart
-  // includes that height. Otherwise keep the chart consistent at
-  // 10 ft.
+  // If a wave height is greater than 10 ft (4 m), make sure the chart
+  // includes that height. Otherwise keep the chart consistent.
   let wave_height_max = wave_heights.reduce(
     (acc, curr) => (curr > acc ? Math.ceil(curr) : acc),
-    10,
+    data.units.system === "metric" ? 4 : 10,
   );
 
   if (wave_height_max & 1) wave_height_max++;
@@ -508,7 +548,7 @@ export function parseForecast(data) {
           : wave_height_labels.slice(start, end),
       datasets: [
         {
-          label: "wave height (feet)",
+          label: `wave height (${data.units.height})`,
           data:
             start === 0
               ? wave_heights.slice(dataStartingAt, end)
@@ -892,3 +932,22 @@ export function parseForecast(data) {
   temperatureForecast.canvas.ontouchmove = tempHover;
   precipitationForecast.canvas.ontouchmove = precipitationHover;
 }
+
+/**
+ * Shows the GLCFS wave height for the hour next to the NWS wave height, hiding it for
+ * hours the model doesn't cover.
+ *
+ * @param {number} x
+ */
+function setGlcfsWaveHeight(x) {
+  const height = glcfs_wave_heights?.[x];
+  const container = document.getElementById("legend-glcfs-container");
+
+  if (height === null || height === undefined) {
+    container?.classList.add("hidden");
+    return;
+  }
+
+  setText("legend-glcfs-wave-height", height);
+  container?.classList.remove("hidden");
+}
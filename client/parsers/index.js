@@ -1,3 +1,5 @@
+export { parseAlerts } from "./alerts";
 export { parseForecast } from "./forecast";
 export { parseRealtime } from "./realtime";
+export { parseSessions } from "./sessions";
 export { parseWaterQuality } from "./waterQuality";
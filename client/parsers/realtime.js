@@ -1,6 +1,7 @@
 import {
   setText,
   setStyleAttribute,
+  setAttribute,
   removeElements,
   nonNull,
   removeElement,
@@ -13,18 +14,95 @@ import {
  * @typedef {Object} LatestData
  * @property {string} quality_color - The hexcode of the quality.
  * @property {string} quality_text - The computed text of the quality.
- * @property {string} water_temp - The latest water temperature.
- * @property {number} wind_direction - The current wind direction.
- * @property {string} wind_speed - The current wind speed.
- * @property {string} gusts - The current wind gust.
- * @property {string} air_temp
- * @property {?string} wave_height
+ * @property {QualityReason} quality_reason - Why the conditions got their quality.
+ * @property {Score} score - The 0-10 rating of the conditions.
+ * @property {?number} water_temp - The latest water temperature, null when no station reported it.
+ * @property {?number} wind_direction - The current wind direction.
+ * @property {?number} wind_speed - The current wind speed.
+ * @property {?number} gusts - The current wind gust.
+ * @property {?string} wind_description - e.g. "Gentle breeze" when the wind is a Beaufort force.
+ * @property {?string} gusts_description
+ * @property {?number} air_temp
+ * @property {?number} wave_height
  * @property {?string} wave_direction
  * @property {?string} wave_period
  * @property {string} as_of
- * @property {boolean} loaded_from_fallback - Wether the latest data used a bouy or land data.
+ * @property {string} observed_at - RFC 3339 time of the oldest reading used.
+ * @property {number} age_minutes - How old the oldest reading used is.
+ * @property {boolean} stale - Wether no station has reported recently.
+ * @property {?WaveComponents} wave_components - Swell and wind waves from the bouy.
+ * @property {RipCurrent} rip_current - The rip current risk at the beach.
+ * @property {Sources} sources - The station each field was taken from.
+ * @property {UnitLabels} units - The units the values are in.
  */
 
+/**
+ * @typedef {Object} UnitLabels
+ * @property {'imperial' | 'metric'} system
+ * @property {'mph' | 'kmh' | 'knots' | 'beaufort'} wind
+ * @property {string} height - e.g. "ft".
+ * @property {string} speed - e.g. "mph", or "Bft" for the Beaufort force.
+ * @property {string} temperature - e.g. "°F".
+ */
+
+/**
+ * @typedef {Object} WaveComponents
+ * @property {WaveComponent} swell
+ * @property {WaveComponent} wind_waves
+ * @property {?string} steepness - e.g. "SWELL" or "VERY_STEEP".
+ */
+
+/**
+ * @typedef {Object} WaveComponent
+ * @property {?number} height
+ * @property {?number} period
+ * @property {?number} direction
+ */
+
+/**
+ * @typedef {Object} RipCurrent
+ * @property {'low' | 'moderate' | 'high'} risk
+ * @property {'nws' | 'estimated'} source - Estimated when the NWS hasn't issued a surf zone forecast.
+ */
+
+/**
+ * @typedef {Object} Sources
+ * @property {?Source} wind
+ * @property {?Source} gusts
+ * @property {?Source} waves - Null when no bouy reported waves.
+ * @property {?Source} water_temp
+ * @property {?Source} air_temp
+ */
+
+/**
+ * @typedef {Object} Source
+ * @property {string} station
+ * @property {number} age_minutes - How old the station's reading is.
+ */
+
+/**
+ * @typedef {Object} Score
+ * @property {number} value - The overall rating, 0-10.
+ * @property {{size: number, wind_speed: number, wind_angle: number, period: number}} breakdown
+ * @property {string} label - The categorical quality, e.g. "Good".
+ * @property {string} color - The hexcode of the quality.
+ */
+
+/**
+ * @typedef {Object} QualityReason
+ * @property {'flat' | 'glassy' | 'offshore' | 'cross_shore' | 'onshore' | 'other_wind' | 'unknown_wind'} rule
+ * @property {boolean} high_wind - Wether the wind was over the spot's high wind threshold.
+ * @property {?('outside_window' | 'windswell' | 'groundswell')} swell - Adjustment made for the swell.
+ * @property {string} text - e.g. "onshore wind 18 mph from 95°".
+ */
+
+/** Colors of each rip current risk, matching the quality colors. */
+const RIP_CURRENT_COLORS = {
+  low: "#0bd674",
+  moderate: "#ffcd1e",
+  high: "#f4496d",
+};
+
 /**
  * Takes the latest data JSON and updates the HTML
  *
@@ -65,40 +143,60 @@ export function parseRealtime(data) {
       `background-color: ${data.quality_color};`,
     );
     setText("wave-quality-text", data.quality_text);
+    setAttribute("wave-quality-text", "title", data.quality_reason.text);
     setStyleAttribute("wave-quality-text", `color: ${data.quality_color}`);
     removeElements(".wave-quality-loader");
   }
 
-  setText("current-water-temp", data.water_temp);
-  setText("current-air-temp", data.air_temp);
-  setText("current-air-temp-2", data.air_temp);
+  setText("current-water-temp", data.water_temp ?? "—");
+  setText("current-air-temp", data.air_temp ?? "—");
+  setText("current-air-temp-2", data.air_temp ?? "—");
 
   setText("wind", getWindData(data));
+  if (data.wind_description) {
+    setText("wind-measurement", `Bft, ${data.wind_description}`);
+  }
   setText("as-of", `As of ${data.as_of}`);
-  setStyleAttribute(
-    "wind-icon",
-    `transform: rotate(${data.wind_direction + 180}deg);`,
-  );
+  if (data.wind_direction != null) {
+    setStyleAttribute(
+      "wind-icon",
+      `transform: rotate(${data.wind_direction + 180}deg);`,
+    );
+    removeHidden("wind-icon-container");
+  }
 
   removeElements(".latest-loader");
-  removeHidden("wind-icon-container");
   removeHidden("wave-icon-container");
   removeStyle("as-of-container", "animate-pulse");
   removeHidden("wave-quality");
 
-  if (data.loaded_from_fallback) {
+  if (data.wave_components) {
+    setText(
+      "wave-components",
+      getWaveComponents(data.wave_components, data.units),
+    );
+    removeHidden("wave-components");
+  }
+
+  if (!data.sources.waves) {
     removeHidden("wave-fallback-icon");
   }
 
-  let oneDayMs = 60 * 60 * 24 * 1_000;
-  let olderThanOneDay = new Date(data.as_of) < new Date() - oneDayMs;
+  setText("rip-current-risk", getRipCurrentText(data.rip_current));
+  setStyleAttribute(
+    "rip-current-risk",
+    `color: ${RIP_CURRENT_COLORS[data.rip_current.risk]};`,
+  );
+  removeHidden("rip-current");
 
-  if (olderThanOneDay) {
+  if (data.stale) {
     setText("as-of", "bouy/weather station down");
     outOfDate("as-of-container");
+    setText("stale-badge", `${formatAge(data.age_minutes)} old`);
+    removeHidden("stale-badge");
   }
 
-  if (data.loaded_from_fallback && olderThanOneDay) {
+  if (!data.sources.wind || data.stale) {
     setText("wind", "---");
     setStyleAttribute("wind-icon-container", "display: none;");
     setText("wind-measurement", "unavailable");
@@ -110,14 +208,55 @@ export function parseRealtime(data) {
   }
 }
 
+/**
+ * Describes the swell and wind waves, e.g. "swell 1.6ft @ 8.3s, wind waves 1.3ft @ 4.5s"
+ *
+ * @param {WaveComponents} components
+ * @param {UnitLabels} units
+ */
+export const getWaveComponents = (components, units) =>
+  [
+    ["swell", components.swell],
+    ["wind waves", components.wind_waves],
+  ]
+    .filter(([, component]) => component.height)
+    .map(
+      ([name, component]) =>
+        `${name} ${component.height}${units.height} @ ${component.period ?? "-"}s`,
+    )
+    .join(", ");
+
+/**
+ * Describes the rip current risk, e.g. "High" or "Moderate (estimated)"
+ *
+ * @param {RipCurrent} ripCurrent
+ */
+export const getRipCurrentText = (ripCurrent) => {
+  const risk = ripCurrent.risk[0].toUpperCase() + ripCurrent.risk.slice(1);
+
+  return ripCurrent.source === "estimated" ? `${risk} (estimated)` : risk;
+};
+
+/**
+ * Formats an age in minutes, e.g. "45 min" or "20 hr"
+ *
+ * @param {number} minutes
+ */
+export const formatAge = (minutes) =>
+  minutes < 60
+    ? `${minutes} min`
+    : minutes < 60 * 48
+      ? `${Math.floor(minutes / 60)} hr`
+      : `${Math.floor(minutes / (60 * 24))} days`;
+
 /**
  * Takes the latest data JSON and creates the wind string
  *
  * @param {LatestData} data
  */
 export const getWindData = (data) =>
-  data.wind_speed === data.gusts
-    ? data.wind_speed
-    : data.gusts === "0"
-      ? data.wind_speed
+  data.wind_speed == null
+    ? "—"
+    : data.wind_speed === data.gusts || !data.gusts
+      ? `${data.wind_speed}`
       : `${data.wind_speed}-${data.gusts}`;
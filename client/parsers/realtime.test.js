@@ -4,14 +4,22 @@ import { getWindData } from "./realtime.js";
 
 describe("getWindData", () => {
   it("getWindData returns just the wind_speed if gusts and wind_speed are the same", () => {
-    assert.strictEqual(getWindData({ wind_speed: "55", gusts: "55" }), "55");
+    assert.strictEqual(getWindData({ wind_speed: 55, gusts: 55 }), "55");
   });
 
   it("getWindData returns just the wind speed if gusts are 0", () => {
-    assert.strictEqual(getWindData({ wind_speed: "56", gusts: "0" }), "56");
+    assert.strictEqual(getWindData({ wind_speed: 56, gusts: 0 }), "56");
   });
 
   it("getWindData returns the wind speed and gusts if they're different", () => {
-    assert.strictEqual(getWindData({ wind_speed: "56", gusts: "57" }), "56-57");
+    assert.strictEqual(getWindData({ wind_speed: 56, gusts: 57 }), "56-57");
+  });
+
+  it("getWindData returns a dash when no station reported the wind", () => {
+    assert.strictEqual(getWindData({ wind_speed: null, gusts: null }), "—");
+  });
+
+  it("getWindData returns just the wind speed without gusts", () => {
+    assert.strictEqual(getWindData({ wind_speed: 56, gusts: null }), "56");
   });
 });
@@ -0,0 +1,46 @@
+import { removeHidden, removeElements, setText } from "../utilities";
+
+/**
+ * @typedef {Object} Session
+ * @property {string} start - When the session starts, RFC 3339.
+ * @property {string} end - When the session ends, RFC 3339.
+ * @property {string} label - e.g. "Tue 06 AM to Tue 10 AM".
+ * @property {number} hours
+ * @property {number} peak_wave_height
+ * @property {number} average_score - The average 0-10 rating over the session.
+ */
+
+/**
+ * @typedef {Object} SessionsData
+ * @property {string} spot
+ * @property {?Session} best - The session with the highest average score.
+ * @property {Session[]} sessions
+ * @property {import("./realtime.js").UnitLabels} units
+ */
+
+/**
+ * Takes the sessions JSON and updates the HTML
+ *
+ * @param {SessionsData} data
+ */
+export function parseSessions(data) {
+  removeElements(".session-loader");
+  removeHidden("best-session");
+
+  if (!data.best) {
+    setText("best-session-label", "No good sessions in the forecast");
+    return;
+  }
+
+  // Whole feet, but metres need the tenths to mean anything.
+  const peak =
+    data.units.system === "metric"
+      ? data.best.peak_wave_height.toFixed(1)
+      : Math.round(data.best.peak_wave_height);
+
+  setText("best-session-label", data.best.label);
+  setText(
+    "best-session-details",
+    `up to ${peak} ${data.units.height}, scoring ${data.best.average_score}`,
+  );
+}
@@ -0,0 +1,207 @@
+use crate::{
+    AppState, Forecast, ForecastSummary, Location, Realtime, ScoreSummary, Spot, UnitLabels, Units,
+    hour_label,
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+use std::{collections::BTreeMap, sync::Arc};
+use tracing::error;
+
+/// The parts of the cached realtime data a comparison needs.
+#[derive(serde::Deserialize)]
+struct LatestSummary {
+    quality_text: String,
+    quality_color: String,
+    score: ScoreSummary,
+    wave_height: Option<f64>,
+}
+
+/// Conditions at several spots side by side.
+#[derive(serde::Serialize, Debug)]
+pub struct Comparison {
+    /// The spots compared, in the order of each hour's conditions
+    pub spots: Vec<Location>,
+    /// Spots with realtime data, best score first
+    pub ranked: Vec<Ranking>,
+    pub hours: Vec<ComparisonHour>,
+    /// The units the values are in
+    pub units: UnitLabels,
+}
+
+/// How a spot is right now.
+#[derive(serde::Serialize, Debug)]
+pub struct Ranking {
+    pub slug: &'static str,
+    pub name: &'static str,
+    pub quality_text: String,
+    pub quality_color: String,
+    pub score: f64,
+    /// In the same units as the forecasted conditions
+    pub wave_height: Option<f64>,
+}
+
+/// The forecasted conditions at every spot for one hour, `None` when a spot's
+/// forecast doesn't cover it.
+#[derive(serde::Serialize, Debug)]
+pub struct ComparisonHour {
+    pub time: String,
+    /// Display friendly time, e.g. "Fri 09 AM"
+    pub label: String,
+    pub conditions: Vec<Option<HourConditions>>,
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct HourConditions {
+    pub quality: String,
+    pub color: String,
+    pub score: f64,
+    pub wave_height: f64,
+}
+
+impl Comparison {
+    /// Gets the realtime data and forecast of every spot concurrently, from the
+    /// Redis cache when they're there. A spot that fails to load is left out of
+    /// the ranking and has no forecasted conditions.
+    pub async fn try_get(
+        spots: Vec<Arc<Spot>>,
+        state: Arc<AppState>,
+        units: Units,
+    ) -> anyhow::Result<Self> {
+        let handles = spots
+            .iter()
+            .map(|spot| {
+                let spot = spot.clone();
+                let state = state.clone();
+                tokio::spawn(async move {
+                    tokio::join!(
+                        Realtime::try_get_string(spot.clone(), state.clone(), units),
+                        Forecast::try_get_string(&spot, state, units)
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut ranked = Vec::with_capacity(spots.len());
+        let mut forecasts = Vec::with_capacity(spots.len());
+        for (spot, handle) in spots.iter().zip(handles) {
+            let (latest, forecast) = handle.await?;
+
+            match latest.and_then(|data| Ok(serde_json::from_str::<LatestSummary>(&data)?)) {
+                Ok(latest) => ranked.push(Ranking {
+                    slug: spot.location.slug,
+                    name: spot.name,
+                    quality_text: latest.quality_text,
+                    quality_color: latest.quality_color,
+                    score: latest.score.value,
+                    wave_height: latest.wave_height,
+                }),
+                Err(e) => error!("Failed to load realtime data for {}: {e}", spot.name),
+            }
+
+            match forecast.and_then(|data| Ok(serde_json::from_str::<ForecastSummary>(&data)?)) {
+                Ok(forecast) => forecasts.push(Some(forecast)),
+                Err(e) => {
+                    error!("Failed to load the forecast for {}: {e}", spot.name);
+                    forecasts.push(None);
+                }
+            }
+        }
+
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        // Hours are labeled in the first spot's time zone, the spots being compared
+        // are along the same stretch of coast.
+        let time_zone = spots
+            .first()
+            .map_or(chrono_tz::UTC, |spot| spot.location.time_zone);
+
+        Ok(Self {
+            spots: spots.iter().map(|spot| spot.location).collect(),
+            ranked,
+            hours: Self::hours(&forecasts, time_zone),
+            units: units.labels(),
+        })
+    }
+
+    /// Lines the forecasts up by the hour, as each spot's forecast can start at
+    /// a different time.
+    fn hours(forecasts: &[Option<ForecastSummary>], time_zone: Tz) -> Vec<ComparisonHour> {
+        let mut hours = BTreeMap::<DateTime<Utc>, Vec<Option<HourConditions>>>::new();
+
+        for (column, forecast) in forecasts.iter().enumerate() {
+            let Some(forecast) = forecast else {
+                continue;
+            };
+            let scores = forecast.scores.as_deref().unwrap_or_default();
+            for (hour, (score, wave_height)) in scores.iter().zip(&forecast.wave_height).enumerate()
+            {
+                let time = forecast.starting_at + TimeDelta::hours(hour as i64);
+                hours
+                    .entry(time)
+                    .or_insert_with(|| vec![None; forecasts.len()])[column] =
+                    Some(HourConditions {
+                        quality: score.label.clone(),
+                        color: score.color.clone(),
+                        score: score.value,
+                        wave_height: *wave_height,
+                    });
+            }
+        }
+
+        hours
+            .into_iter()
+            .map(|(time, conditions)| ComparisonHour {
+                time: time.to_rfc3339(),
+                label: hour_label(time, time_zone),
+                conditions,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono_tz::US::Central;
+
+    fn forecast(starting_at: &str, hours: &[(&str, f64)]) -> Option<ForecastSummary> {
+        Some(ForecastSummary {
+            starting_at: starting_at.parse().unwrap(),
+            wave_height: hours.iter().map(|(_, height)| *height).collect(),
+            scores: Some(
+                hours
+                    .iter()
+                    .map(|(label, _)| ScoreSummary {
+                        value: 5.0,
+                        label: label.to_string(),
+                        color: String::new(),
+                    })
+                    .collect(),
+            ),
+        })
+    }
+
+    #[test]
+    fn hours_line_up_forecasts_starting_at_different_times() {
+        let hours = Comparison::hours(
+            &[
+                forecast("2024-06-10T15:00:00+00:00", &[("Good", 3.0), ("Poor", 2.0)]),
+                None,
+                forecast("2024-06-10T16:00:00+00:00", &[("Flat", 0.5)]),
+            ],
+            Central,
+        );
+
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].label, "Mon 10 AM");
+        assert_eq!(hours[0].conditions[0].as_ref().unwrap().quality, "Good");
+        assert!(hours[0].conditions[1].is_none());
+        assert!(hours[0].conditions[2].is_none());
+
+        assert_eq!(hours[1].time, "2024-06-10T16:00:00+00:00");
+        assert_eq!(hours[1].conditions[0].as_ref().unwrap().wave_height, 2.0);
+        assert_eq!(hours[1].conditions[2].as_ref().unwrap().quality, "Flat");
+    }
+}
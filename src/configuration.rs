@@ -1,3 +1,6 @@
+use crate::{DirectionWindow, QualityProfile};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, de::Error};
 use serde_aux::field_attributes::deserialize_number_from_string;
 
 #[derive(serde::Deserialize)]
@@ -6,6 +9,9 @@ pub struct Settings {
     pub forecast_api: DataAPI,
     pub realtime_api: DataAPI,
     pub quality_api: DataAPI,
+    pub water_level_api: DataAPI,
+    pub glcfs_api: DataAPI,
+    pub spots: Vec<SpotSettings>,
 }
 
 #[derive(serde::Deserialize)]
@@ -13,6 +19,59 @@ pub struct DataAPI {
     pub base_url: String,
 }
 
+/// A single break as declared in `config/spots.yml`.
+#[derive(serde::Deserialize)]
+pub struct SpotSettings {
+    pub name: String,
+    pub slug: String,
+    pub region: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Compass bearing, in degrees, the beach faces
+    pub bearing: f64,
+    /// IANA time zone times at the spot are shown in, defaults to US Central
+    #[serde(
+        default = "default_time_zone",
+        deserialize_with = "deserialize_time_zone"
+    )]
+    pub time_zone: Tz,
+    /// Directions swell reaches the beach from, defaults to 90° either side
+    /// of the bearing
+    pub swell_window: Option<DirectionWindow>,
+    /// NWS gridpoint, e.g. "MKX/90,67", resolved from the coordinates when missing
+    pub gridpoint: Option<String>,
+    /// NDBC station ids, e.g. "45013", in order of preference
+    pub stations: Vec<String>,
+    /// Minutes after which the latest observation is stale, defaults to a day
+    pub max_age_minutes: Option<i64>,
+    /// NWS nearshore marine zone, e.g. "LMZ645"
+    pub marine_zone: Option<String>,
+    /// NWS public zone beach hazards are issued for, e.g. "WIZ066"
+    pub beach_zone: Option<String>,
+    /// NOAA CO-OPS water level station id, e.g. "9087057"
+    pub water_level_station: Option<String>,
+    /// ERDDAP dataset of the lake's GLCFS wave model output
+    pub glcfs_dataset: Option<String>,
+    /// ArcGIS object id of the beach monitoring location
+    pub beach_id: String,
+    pub live_feed_url: Option<String>,
+    /// How the beach reacts to the wind
+    pub quality_profile: QualityProfile,
+    pub has_bouy: bool,
+}
+
+fn default_time_zone() -> Tz {
+    chrono_tz::US::Central
+}
+
+/// Parses an IANA time zone name, e.g. "America/Chicago".
+fn deserialize_time_zone<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Tz, D::Error> {
+    let name = String::deserialize(deserializer)?;
+
+    name.parse()
+        .map_err(|_| D::Error::custom(format!("unknown time zone {name}")))
+}
+
 #[derive(serde::Deserialize)]
 pub struct ApplicationSettings {
     #[serde(deserialize_with = "deserialize_number_from_string")]
@@ -33,6 +92,7 @@ pub fn get_configuration() -> Result<Settings, config::ConfigError> {
     let environment_filename = format!("{}.yml", env.as_str());
     let settings = config::Config::builder()
         .add_source(config::File::from(config_directory.join("base.yml")))
+        .add_source(config::File::from(config_directory.join("spots.yml")))
         .add_source(config::File::from(
             config_directory.join(environment_filename),
         ))
@@ -1,87 +1,293 @@
 use std::{cmp::Ordering, sync::Arc};
 
-use super::{Location, Spot};
-use crate::{AppState, utils::*};
+use super::{
+    Alert, Conditions, ForecastProvider, Glcfs, HourlyForecast, Length, Location, MarineForecast,
+    Nws, QualityReason, Score, Speed, Spot, System, Temperature, UnitLabels, Units, Wind,
+};
+use crate::{AppState, realtime::headed_toward, utils::*};
 
 use anyhow::{anyhow, bail};
-use chrono::{DateTime, Utc};
-use chrono_tz::US::Central;
-use reqwest::Response;
-use tracing::{error, info, warn};
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
+use chrono::{DateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+use tracing::warn;
 
 #[derive(serde::Serialize)]
 pub struct Forecast {
-    pub as_of: String,
-    pub cloud_cover: Vec<u8>,
+    pub as_of: Option<String>,
+    pub cloud_cover: Vec<Option<u8>>,
     pub current_wave_height: String,
-    pub current_wave_period: f64,
-    pub current_wave_direction: f64,
-    pub dewpoint: Vec<String>,
-    pub probability_of_precipitation: Vec<u8>,
-    pub probability_of_thunder: Vec<u8>,
+    pub current_wave_period: Option<f64>,
+    pub current_wave_direction: Option<f64>,
+    pub dewpoint: Vec<Option<i8>>,
+    pub probability_of_precipitation: Vec<Option<u8>>,
+    pub probability_of_thunder: Vec<Option<u8>>,
     pub quality: Option<Vec<String>>,
-    pub starting_at: String,
-    pub temperature: Vec<i8>,
+    pub quality_reasons: Option<Vec<QualityReason>>,
+    pub scores: Option<Vec<Score>>,
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub starting_at: DateTime<Utc>,
+    pub temperature: Vec<Option<i8>>,
     #[serde(skip_serializing)]
-    pub wave_direction: Vec<f64>,
+    pub wave_direction: Vec<Option<f64>>,
     pub wave_height: Vec<f64>,
+    /// Display friendly hours in the spot's time zone, e.g. "Fri 09 AM"
     pub wave_height_labels: Vec<String>,
-    pub wave_period: Vec<f64>,
+    /// The hour each value is forecast for, RFC 3339 in the API
+    #[serde(serialize_with = "serialize_rfc3339_series")]
+    pub times: Vec<DateTime<Utc>>,
+    /// `None` for hours the NWS has no value
+    pub wave_period: Vec<Option<f64>>,
     pub wind_speed: Vec<f64>,
-    pub wind_gust: Vec<f64>,
+    pub wind_gust: Vec<Option<f64>>,
+    /// Descriptions of the wind speeds when they're Beaufort forces
+    pub wind_speed_description: Option<Vec<&'static str>>,
+    pub wind_gust_description: Option<Vec<Option<&'static str>>>,
     pub wind_direction: Vec<f64>,
+    /// The nearshore marine forecast text for the spot's zone
+    pub marine_forecast: Option<MarineForecast>,
+    /// Hazards active at the spot, e.g. Small Craft Advisories
+    pub alerts: Vec<Alert>,
+    /// Waves forecast by other models, e.g. GLCFS, for the same hours
+    pub alternates: Vec<AlternateForecast>,
+    /// The units the values are in
+    pub units: UnitLabels,
+}
+
+/// The parts of a cached forecast that comparisons and sessions are found in.
+#[derive(serde::Deserialize)]
+pub(crate) struct ForecastSummary {
+    #[serde(deserialize_with = "deserialize_rfc3339")]
+    pub starting_at: DateTime<Utc>,
+    pub wave_height: Vec<f64>,
+    pub scores: Option<Vec<ScoreSummary>>,
+}
+
+/// A [`Score`] as it's cached.
+#[derive(serde::Deserialize)]
+pub(crate) struct ScoreSummary {
+    pub value: f64,
+    pub label: String,
+    pub color: String,
+}
+
+/// Waves forecast by another model, e.g. GLCFS, lined up with the hours of the
+/// NWS forecast.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct AlternateForecast {
+    /// e.g. "GLCFS"
+    pub source: &'static str,
+    /// `None` for hours the model doesn't cover
+    pub wave_height: Vec<Option<f64>>,
+    pub wave_period: Vec<Option<f64>>,
+    pub wave_direction: Vec<Option<f64>>,
+}
+
+impl AlternateForecast {
+    /// Lines the model's hours up with the `hours` starting at `starting_at`,
+    /// leaving the hours it doesn't cover empty.
+    pub fn new(
+        source: &'static str,
+        hourly: &HourlyForecast,
+        starting_at: DateTime<Utc>,
+        hours: usize,
+        units: Units,
+    ) -> Self {
+        // The model's hour for each of the `hours`
+        let index = |hour: usize| {
+            let time = starting_at + TimeDelta::hours(hour as i64);
+            usize::try_from((time - hourly.starting_at).num_hours()).ok()
+        };
+        let aligned = |series: &[Option<f64>]| {
+            (0..hours)
+                .map(|hour| index(hour).and_then(|index| series.get(index).copied().flatten()))
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            source,
+            wave_height: (0..hours)
+                .map(|hour| index(hour).and_then(|index| hourly.wave_height.get(index)))
+                .map(|v| v.map(|v| truncate_to_two_decimals(units.height(Length::from_meters(*v)))))
+                .collect(),
+            wave_period: aligned(&hourly.wave_period),
+            wave_direction: aligned(&hourly.wave_direction),
+        }
+    }
 }
 
 impl Forecast {
-    pub async fn try_get_string(spot: &Spot, state: Arc<AppState>) -> anyhow::Result<String> {
-        if let Some(data) =
-            redis_utils::get(&format!("forecast-{}", spot.name), &state.redis_pool).await
-        {
+    pub async fn try_get_string(
+        spot: &Spot,
+        state: Arc<AppState>,
+        units: Units,
+    ) -> anyhow::Result<String> {
+        let cache_key = format!("forecast-{}-{units}", spot.name);
+        if let Some(data) = redis_utils::get(&cache_key, &state.redis_pool).await {
             tracing::info!("redis cache hit!");
             return Ok(data);
         }
 
-        let data = Self::try_get(spot, state.forecast_url).await?;
+        let data = Self::try_get(
+            spot,
+            state.forecast_url,
+            state.glcfs_url,
+            &state.redis_pool,
+            units,
+        )
+        .await?;
         let data = serde_json::to_string(&data)?;
 
-        redis_utils::set(&format!("forecast-{}", spot.name), &data, &state.redis_pool).await?;
+        redis_utils::cache(&cache_key, &data, &state.redis_pool).await;
 
         Ok(data)
     }
 
-    pub async fn try_get(spot: &Spot, forecast_url: &str) -> anyhow::Result<Self> {
-        let data = Self::fetch_data(spot.forecast_path, forecast_url).await?;
+    /// Gets the NWS forecast for the spot along with the other models covering it.
+    pub async fn try_get(
+        spot: &Spot,
+        forecast_url: &str,
+        glcfs_url: &str,
+        redis_pool: &Pool<RedisConnectionManager>,
+        units: Units,
+    ) -> anyhow::Result<Self> {
+        let nws = Nws {
+            forecast_url,
+            redis_pool,
+        };
+        let glcfs = Glcfs { glcfs_url };
+        let (hourly, glcfs_hourly, marine_forecast, alerts) = tokio::join!(
+            nws.try_get_hourly(spot),
+            glcfs.try_get_hourly(spot),
+            MarineForecast::try_get(spot, forecast_url),
+            Alert::try_get_active(spot, forecast_url),
+        );
 
-        let mut forecast: Self = (data.json::<serde_json::Value>().await?).try_into()?;
+        let hourly = hourly?.ok_or(anyhow!("{} has no NWS forecast", spot.name))?;
+        let mut forecast = Self::try_from_hourly(&hourly, units, spot.location.time_zone)?;
 
         forecast.condense();
-        forecast.compute_quality(&spot.location);
+        forecast.compute_quality(&hourly, &spot.location);
+
+        // The gridpoint forecast is still good without the marine forecast or alerts.
+        forecast.marine_forecast = marine_forecast.unwrap_or_else(|e| {
+            warn!("Unable to get the marine forecast for {}: {e}", spot.name);
+            None
+        });
+        forecast.alerts = alerts.unwrap_or_else(|e| {
+            warn!("Unable to get the alerts for {}: {e}", spot.name);
+            vec![]
+        });
+
+        // As are the other models.
+        match glcfs_hourly {
+            Ok(Some(glcfs_hourly)) => forecast.alternates.push(AlternateForecast::new(
+                glcfs.name(),
+                &glcfs_hourly,
+                hourly.starting_at,
+                forecast.wave_height.len(),
+                units,
+            )),
+            Ok(None) => {}
+            Err(e) => warn!("Unable to get the GLCFS forecast for {}: {e}", spot.name),
+        }
 
         Ok(forecast)
     }
 
-    async fn fetch_data(forecast_path: &str, forecast_url: &str) -> anyhow::Result<Response> {
-        let client = reqwest::Client::builder()
-            .user_agent("GatheringSurf/0.1 (+https://gathering.surf)")
-            .build()
-            .unwrap();
-
-        const RETRY: u8 = 2;
-        for _ in 0..RETRY {
-            let response = client
-                .get(format!("{forecast_url}{forecast_path}"))
-                .timeout(std::time::Duration::from_secs(10))
-                .send()
-                .await?;
-            if response.status().as_u16() == 200 {
-                info!("NOAA 200 success.");
-                return Ok(response);
-            }
-            warn!("NOAA non-200, retrying: {}{}", forecast_url, forecast_path);
-        }
+    /// Assembles the forecast from any provider's hourly series, in the units
+    /// it's displayed in and labeled in the spot's `time_zone`.
+    pub fn try_from_hourly(
+        hourly: &HourlyForecast,
+        units: Units,
+        time_zone: Tz,
+    ) -> anyhow::Result<Self> {
+        let as_of = hourly
+            .updated_at
+            .map(|updated_at| updated_at.with_timezone(&time_zone).to_rfc2822());
+        let starting_at = hourly.starting_at;
+
+        let heights = hourly
+            .wave_height
+            .iter()
+            .map(|v| units.height(Length::from_meters(*v)))
+            .collect::<Vec<_>>();
+        let wave_height = Self::smooth_wave_data(&heights);
+        let speed =
+            |v: f64| truncate_to_two_decimals(units.speed(Speed::from_meters_per_second(v)));
+        let description = |v: f64| Speed::from_meters_per_second(v).beaufort().description;
+        let beaufort = units.wind == Wind::Beaufort;
+        let temperatures = |series: &[Option<f64>]| {
+            series
+                .iter()
+                .map(|v| v.map(|v| units.temperature(Temperature::from_celsius(v)).round() as i8))
+                .collect::<Vec<_>>()
+        };
+        let percents = |series: &[Option<f64>]| {
+            series
+                .iter()
+                .map(|v| v.map(|v| v as u8))
+                .collect::<Vec<_>>()
+        };
 
-        error!("Non 200 response from NOAA");
-        bail!("Non 200 response from NOAA");
+        let times = (0..hourly.wave_height.len())
+            .map(|hour| starting_at + TimeDelta::hours(hour as i64))
+            .collect::<Vec<_>>();
+        let wave_height_labels = times
+            .iter()
+            .map(|time| hour_label(*time, time_zone))
+            .collect();
+
+        let (current_wave_height, current_wave_period, current_wave_direction) =
+            Self::get_current_wave_data(
+                &wave_height,
+                &hourly.wave_period,
+                &hourly.wave_direction,
+                starting_at,
+                units,
+            )?;
+
+        Ok(Self {
+            as_of,
+            wave_height,
+            wave_direction: hourly.wave_direction.clone(),
+            wave_period: hourly.wave_period.clone(),
+            wind_speed: hourly.wind_speed.iter().map(|v| speed(*v)).collect(),
+            wind_gust: hourly.wind_gust.iter().map(|v| v.map(speed)).collect(),
+            wind_speed_description: beaufort
+                .then(|| hourly.wind_speed.iter().map(|v| description(*v)).collect()),
+            wind_gust_description: beaufort.then(|| {
+                hourly
+                    .wind_gust
+                    .iter()
+                    .map(|v| v.map(description))
+                    .collect()
+            }),
+            wind_direction: hourly
+                .wind_direction
+                .iter()
+                .map(|v| truncate_to_two_decimals(*v))
+                .collect(),
+            quality: None,
+            quality_reasons: None,
+            scores: None,
+            temperature: temperatures(&hourly.temperature),
+            probability_of_precipitation: percents(&hourly.probability_of_precipitation),
+            dewpoint: temperatures(&hourly.dewpoint),
+            cloud_cover: percents(&hourly.cloud_cover),
+            probability_of_thunder: percents(&hourly.probability_of_thunder),
+            starting_at,
+            wave_height_labels,
+            times,
+            current_wave_period,
+            current_wave_height,
+            current_wave_direction,
+            marine_forecast: None,
+            alerts: vec![],
+            alternates: vec![],
+            units: units.labels(),
+        })
     }
 
     /// Condenses the forecast to equal length vecs.
@@ -102,41 +308,65 @@ impl Forecast {
         let _ = self.wave_height.split_off(*min);
         let _ = self.wind_speed.split_off(*min);
         let _ = self.wind_gust.split_off(*min);
+        for descriptions in [
+            &mut self.wind_speed_description,
+            &mut self.wind_gust_description,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            descriptions.truncate(*min);
+        }
         let _ = self.wind_direction.split_off(*min);
         let _ = self.wave_height.split_off(*min);
         let _ = self.wave_height_labels.split_off(*min);
+        let _ = self.times.split_off(*min);
     }
 
-    /// Takes the relative attributes and computes their quality
-    pub fn compute_quality(&mut self, location: &Location) {
+    /// Takes the relative attributes and computes their quality, why, and score.
+    /// Quality is rated in feet and mph whatever the display units, the wind
+    /// from the `hourly` series it was displayed from.
+    pub fn compute_quality(&mut self, hourly: &HourlyForecast, location: &Location) {
+        let units = Units::new(self.units.system, None);
         let mut qualities = Vec::with_capacity(self.wind_direction.len());
-        for ((wind_direction, wind_speed), wave_height) in self
+        let mut reasons = Vec::with_capacity(self.wind_direction.len());
+        let mut scores = Vec::with_capacity(self.wind_direction.len());
+        for ((((wind_direction, wind_speed), wave_height), wave_period), wave_direction) in self
             .wind_direction
             .iter()
-            .zip(self.wind_speed.iter())
+            .zip(hourly.wind_speed.iter())
             .zip(self.wave_height.iter())
+            .zip(self.wave_period.iter())
+            .zip(self.wave_direction.iter())
         {
-            qualities.push(
-                location
-                    .get_quality(*wave_height, *wind_speed, *wind_direction)
-                    .1
-                    .to_string(),
-            );
+            let conditions = Conditions {
+                wave_height: Some(units.to_length(*wave_height).feet()),
+                wave_period: *wave_period,
+                wave_direction: *wave_direction,
+                wind_speed: Some(truncate_to_two_decimals(
+                    Speed::from_meters_per_second(*wind_speed).miles_per_hour(),
+                )),
+                wind_direction: Some(*wind_direction),
+            };
+            let rating = location.get_quality(&conditions);
+            let score = location.get_score(&conditions, &rating);
+
+            qualities.push(score.color.to_string());
+            reasons.push(rating.reason);
+            scores.push(score);
         }
 
-        self.quality = Some(qualities)
+        self.quality = Some(qualities);
+        self.quality_reasons = Some(reasons);
+        self.scores = Some(scores);
     }
 
     /// Smooths the wave data by taking the average of three data points, turns data
     /// from something like [0,0,1,2] into [.33, 1, 1.5, 2] to better show growing wave heights.
     fn smooth_wave_data(wave_height: &[f64]) -> Vec<f64> {
-        let mut smoothed_data = Vec::with_capacity(wave_height.len());
         let mut out = Vec::with_capacity(wave_height.len());
-        wave_height
-            .iter()
-            .for_each(|data| smoothed_data.push(convert_meter_to_feet(*data)));
 
-        smoothed_data.windows(3).for_each(|window| match window {
+        wave_height.windows(3).for_each(|window| match window {
             [x, y, z] => out.push(truncate_to_two_decimals((x + y + z) / 3.0)),
             [x, y] => out.push(truncate_to_two_decimals((x + y) / 2.0)),
             [x] => out.push(truncate_to_two_decimals(*x)),
@@ -147,13 +377,15 @@ impl Forecast {
     }
 
     /// Returns the wave height, period and direction from the forecasted
-    /// data relative to the time of request.
+    /// data relative to the time of request. Heights are in whole feet or
+    /// tenths of a metre.
     fn get_current_wave_data(
         wave_height: &[f64],
-        wave_period: &[f64],
-        wave_direction: &[f64],
-        starting_at: &str,
-    ) -> anyhow::Result<(String, f64, f64)> {
+        wave_period: &[Option<f64>],
+        wave_direction: &[Option<f64>],
+        starting_at: DateTime<Utc>,
+        units: Units,
+    ) -> anyhow::Result<(String, Option<f64>, Option<f64>)> {
         // Required for unit tests to have a consistent as of time
         #[cfg(not(feature = "mock-time"))]
         let current_time_index = Self::get_current_time_index(starting_at)? + 1;
@@ -164,246 +396,181 @@ impl Forecast {
             bail!("Invalid accessing index found!");
         }
 
-        let height = *wave_height.get(current_time_index).unwrap() as u8;
-        let period = wave_period.get(current_time_index).unwrap();
-        let direction = wave_direction.get(current_time_index).unwrap() + 180.0;
+        let precision = match units.system {
+            System::Imperial => 0,
+            System::Metric => 1,
+        };
+        let scale = 10_f64.powi(precision as i32);
+        let round_down = |v: f64| (v * scale).trunc() / scale;
+
+        let height = round_down(*wave_height.get(current_time_index).unwrap());
+        let period = wave_period.get(current_time_index).copied().flatten();
+        let direction = wave_direction
+            .get(current_time_index)
+            .copied()
+            .flatten()
+            .map(|v| f64::from(headed_toward(v)));
 
         // Try to get range of current surf
         if let Some(last_hour) = wave_height.get(current_time_index - 1) {
-            let last_hour = *last_hour as u8;
+            let last_hour = round_down(*last_hour);
             return match height.partial_cmp(&last_hour) {
-                Some(Ordering::Less) => {
-                    Ok((format!("{height:.0}-{last_hour:.0}"), *period, direction))
-                }
-                Some(Ordering::Greater) => {
-                    Ok((format!("{last_hour:.0}-{height:.0}+"), *period, direction))
-                }
-                Some(Ordering::Equal) => Ok((format!("{height:.0}"), *period, direction)),
+                Some(Ordering::Less) => Ok((
+                    format!("{height:.precision$}-{last_hour:.precision$}"),
+                    period,
+                    direction,
+                )),
+                Some(Ordering::Greater) => Ok((
+                    format!("{last_hour:.precision$}-{height:.precision$}+"),
+                    period,
+                    direction,
+                )),
+                Some(Ordering::Equal) => Ok((format!("{height:.precision$}"), period, direction)),
                 None => unreachable!("Found no ordering in wave heights."),
             };
         }
 
-        Ok((format!("{height:.0}"), *period, direction))
+        Ok((format!("{height:.precision$}"), period, direction))
     }
 
     #[cfg(not(feature = "mock-time"))]
-    fn get_current_time_index(starting_at: &str) -> anyhow::Result<usize> {
-        Ok((Utc::now()
-            - DateTime::parse_from_str(starting_at, "%+")
-                .unwrap()
-                .with_timezone(&Utc))
-        .num_hours()
-        .try_into()?)
-    }
-
-    fn try_from_value<T: std::clone::Clone>(
-        properties: &serde_json::Value,
-        key: &str,
-        f: &dyn Fn(f64) -> T,
-    ) -> anyhow::Result<Vec<T>> {
-        Ok(properties
-            .get(key)
-            .ok_or(anyhow!("no {key} found!"))?
-            .get("values")
-            .ok_or(anyhow!("no values found!"))?
-            .as_array()
-            .ok_or(anyhow!("array not found!"))?
-            .clone()
-            .into_iter()
-            .flat_map(|value| Self::expand_and_convert(&value, f).unwrap())
-            .collect())
+    fn get_current_time_index(starting_at: DateTime<Utc>) -> anyhow::Result<usize> {
+        Ok((Utc::now() - starting_at).num_hours().try_into()?)
     }
+}
 
-    fn expand_and_convert<T: std::clone::Clone>(
-        v: &serde_json::Value,
-        f: &dyn Fn(f64) -> T,
-    ) -> anyhow::Result<Vec<T>> {
-        let (value, valid_time) = Self::get_value_and_time(v)?;
-
-        let (_, period) = valid_time
-            .split_once("/P")
-            .ok_or(anyhow!("Unknown period found!"))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let period_len = Self::parse_period(period);
+    use chrono::DurationRound;
+    use chrono_tz::US::Central;
 
-        Ok(vec![f(value); period_len])
+    fn meters(feet: &[f64]) -> Vec<f64> {
+        feet.iter()
+            .map(|v| Length::from_feet(*v).meters())
+            .collect()
     }
 
-    /// Extracts the value and it's time attribute from the object,
-    /// e.g. {
-    ///     "value": 5.3,
-    ///     "validTime": "2024-07-18T05:00:00+00:00/P1D5H"
-    /// } -> (5.3, "2024-07-18T05:00:00+00:00/P1D5H")
-    fn get_value_and_time(v: &serde_json::Value) -> anyhow::Result<(f64, &str)> {
-        let value = v
-            .get("value")
-            .ok_or(anyhow!("No value found."))?
-            .as_f64()
-            .ok_or(anyhow!("Not an f64"))?;
-        let valid_time = v
-            .get("validTime")
-            .ok_or(anyhow!("No validTime found."))?
-            .as_str()
-            .unwrap();
-
-        Ok((value, valid_time))
+    /// A forecast starting this hour, as the current wave data is relative to now.
+    fn hourly(hours: usize) -> HourlyForecast {
+        let starting_at = Utc::now().duration_trunc(TimeDelta::hours(1)).unwrap();
+        let mph = |v| Speed::from_miles_per_hour(v).meters_per_second();
+
+        HourlyForecast {
+            updated_at: Some(starting_at),
+            starting_at,
+            wave_height: meters(&[0.0, 0.0, 1.0, 2.0, 3.0][..hours]),
+            wave_period: vec![Some(6.0); hours],
+            wave_direction: vec![Some(90.0); hours],
+            wind_speed: vec![mph(7.4521); hours],
+            wind_gust: vec![Some(mph(12.0)); hours],
+            wind_direction: vec![270.0; hours],
+            temperature: vec![Some(16.0); hours],
+            dewpoint: vec![Some(6.7); hours],
+            probability_of_precipitation: vec![Some(20.0); hours],
+            probability_of_thunder: vec![Some(0.0); hours],
+            cloud_cover: vec![Some(75.0); hours],
+        }
     }
 
-    /// Parses the period into its length in hours,
-    /// e.g. 1DT5H -> 29
-    fn parse_period(period: &str) -> usize {
-        let mut period_len = 0;
-
-        if let Some((day, hour)) = period.split_once('D') {
-            period_len += day.parse::<usize>().unwrap() * 24;
-            period_len += parse_hour(hour).unwrap_or(0);
-        } else {
-            period_len += parse_hour(period).unwrap_or(0);
-        };
+    #[test]
+    fn the_forecast_is_assembled_from_any_hourly_series() {
+        let hourly = hourly(5);
+        let forecast = Forecast::try_from_hourly(&hourly, Units::IMPERIAL, Central).unwrap();
 
-        period_len
+        assert_eq!(forecast.starting_at, hourly.starting_at);
+        assert_eq!(forecast.times[4], hourly.starting_at + TimeDelta::hours(4));
+        assert_eq!(
+            forecast.wave_height_labels[4],
+            hour_label(forecast.times[4], Central)
+        );
+        assert_eq!(forecast.current_wave_height, "0-1+");
+        assert_eq!(forecast.wind_speed[0], 7.45);
+        assert_eq!(forecast.temperature[0], Some(61));
+        assert_eq!(forecast.dewpoint[0], Some(44));
+        assert_eq!(forecast.cloud_cover[0], Some(75));
     }
 
-    // Try to write this better !
-    /// Expands and creates the time labels from the wave_height data
-    /// in the JSON. e.g. ["Fri 10 AM", "Fri 11 AM", ...]
-    fn try_labels(properties: &serde_json::Value) -> anyhow::Result<Vec<String>> {
-        Ok(properties
-            .get("waveHeight")
-            .ok_or(anyhow!("no waveHeight found!"))?
-            .get("values")
-            .ok_or(anyhow!("no values found!"))?
-            .as_array()
-            .ok_or(anyhow!("array not found!"))?
-            .clone()
-            .into_iter()
-            .flat_map(|value| {
-                let (_, valid_time) = Self::get_value_and_time(&value).unwrap();
-
-                let (time, period) = valid_time
-                    .split_once("/P")
-                    .ok_or(anyhow!("Unknown period found!"))
-                    .unwrap();
-
-                let period_len = Self::parse_period(period);
-                let mut labels = Vec::with_capacity(period_len);
-
-                for i in 0..period_len {
-                    labels.push(increment_time(time, i).unwrap());
-                }
-
-                labels
-            })
-            .collect())
+    #[test]
+    fn hours_without_a_value_are_empty() {
+        let mut hourly = hourly(5);
+        hourly.wave_period = vec![None; 5];
+        hourly.probability_of_thunder = vec![None; 5];
+        let forecast = Forecast::try_from_hourly(&hourly, Units::IMPERIAL, Central).unwrap();
+
+        assert_eq!(forecast.current_wave_period, None);
+        assert_eq!(forecast.probability_of_thunder, [None; 5]);
     }
-}
-
-impl TryFrom<serde_json::Value> for Forecast {
-    type Error = anyhow::Error;
-
-    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
-        let properties = value
-            .get("properties")
-            .ok_or(anyhow!("no properties found!"))?;
-
-        let as_of = properties
-            .get("updateTime")
-            .ok_or(anyhow!("no updateTime found"))?
-            .as_str()
-            .ok_or(anyhow!("string not found"))?
-            .parse::<DateTime<Utc>>()?
-            .with_timezone(&Central)
-            .to_rfc2822()
-            .to_string();
-
-        let wave_height =
-            Self::smooth_wave_data(&Self::try_from_value(properties, "waveHeight", &|v| v)?);
-
-        let wave_period = Self::try_from_value(properties, "wavePeriod", &|v| v)?;
-        let wave_direction = Self::try_from_value(properties, "waveDirection", &|v| v)?;
-        let wind_speed = Self::try_from_value(properties, "windSpeed", &|v| {
-            truncate_to_two_decimals(convert_kilo_meter_to_mile(v))
-        })?;
-        let wind_gust = Self::try_from_value(properties, "windGust", &|v| {
-            truncate_to_two_decimals(convert_kilo_meter_to_mile(v))
-        })?;
-        let wind_direction = Self::try_from_value(properties, "windDirection", &|v| {
-            truncate_to_two_decimals(v)
-        })?;
-        let temperature = Self::try_from_value(properties, "temperature", &|v| {
-            convert_celsius_to_fahrenheit(v).parse::<i8>().unwrap()
-        })?;
-        let probability_of_precipitation =
-            Self::try_from_value(properties, "probabilityOfPrecipitation", &|v| v as u8)?;
-        let dewpoint = Self::try_from_value(properties, "dewpoint", &|v| {
-            convert_celsius_to_fahrenheit(v)
-        })?;
-        let cloud_cover = Self::try_from_value(properties, "skyCover", &|v| v as u8)?;
-        let probability_of_thunder =
-            Self::try_from_value(properties, "probabilityOfThunder", &|v| v as u8)?;
-        let wave_height_labels = Self::try_labels(properties)?;
-
-        let starting_at = properties
-            .get("validTimes")
-            .unwrap()
-            .as_str()
-            .unwrap()
-            .split_once('/')
-            .unwrap()
-            .0;
 
-        let (current_wave_height, current_wave_period, current_wave_direction) =
-            Self::get_current_wave_data(&wave_height, &wave_period, &wave_direction, starting_at)?;
+    #[test]
+    fn the_current_wave_direction_is_where_the_waves_are_headed() {
+        let mut hourly = hourly(5);
+        hourly.wave_direction = vec![Some(270.0); 5];
+        let forecast = Forecast::try_from_hourly(&hourly, Units::IMPERIAL, Central).unwrap();
 
-        Ok(Self {
-            as_of,
-            wave_height,
-            wave_direction,
-            wave_period,
-            wind_speed,
-            wind_gust,
-            wind_direction,
-            quality: None,
-            temperature,
-            probability_of_precipitation,
-            dewpoint,
-            cloud_cover,
-            probability_of_thunder,
-            starting_at: starting_at.to_string(),
-            wave_height_labels,
-            current_wave_period,
-            current_wave_height,
-            current_wave_direction,
-        })
+        assert_eq!(forecast.current_wave_direction, Some(90.0));
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn parse_period_parses_an_hours_only_string() {
-        assert_eq!(Forecast::parse_period("T2H"), 2);
+    fn wave_heights_are_smoothed_over_three_hours() {
+        let mut forecast = Forecast::try_from_hourly(&hourly(5), Units::IMPERIAL, Central).unwrap();
+        forecast.condense();
+
+        assert_eq!(forecast.wave_height, [0.33, 1.0, 2.0]);
+        assert_eq!(forecast.wave_height_labels.len(), 3);
+        assert_eq!(forecast.wind_speed.len(), 3);
     }
+
     #[test]
-    fn parse_period_parses_an_hours_and_days_string() {
-        assert_eq!(Forecast::parse_period("2DT10H"), 58);
+    fn alternates_line_up_with_the_forecast_hours() {
+        let mut model = hourly(3);
+        model.starting_at += TimeDelta::hours(1);
+        model.wave_height = meters(&[1.44, 1.77, 2.29]);
+
+        let alternate =
+            AlternateForecast::new("GLCFS", &model, hourly(3).starting_at, 5, Units::IMPERIAL);
+
+        assert_eq!(
+            alternate.wave_height,
+            [None, Some(1.44), Some(1.77), Some(2.29), None]
+        );
+        assert_eq!(alternate.wave_period[1], Some(6.0));
     }
+
     #[test]
-    fn parse_period_parses_an_empty_string() {
-        assert_eq!(Forecast::parse_period(""), 0);
+    fn the_forecast_can_be_metric() {
+        let forecast = Forecast::try_from_hourly(&hourly(5), Units::METRIC, Central).unwrap();
+
+        assert_eq!(forecast.wave_height[..3], [0.1, 0.3, 0.6]);
+        assert_eq!(forecast.current_wave_height, "0.1-0.3+");
+        assert_eq!(forecast.wind_speed[0], 11.99);
+        assert_eq!(forecast.temperature[0], Some(16));
+        assert_eq!(forecast.units.speed, "km/h");
     }
 
     #[test]
-    fn get_value_and_time_gets_values() {
+    fn the_wind_can_be_in_knots_or_beaufort() {
+        let knots = Units::new(System::Imperial, Some(Wind::Knots));
+        let forecast = Forecast::try_from_hourly(&hourly(5), knots, Central).unwrap();
+
+        assert_eq!(forecast.wind_speed[0], 6.47);
+        assert_eq!(forecast.wind_speed_description, None);
+        assert_eq!(forecast.units.speed, "kn");
+
+        let beaufort = Units::new(System::Imperial, Some(Wind::Beaufort));
+        let forecast = Forecast::try_from_hourly(&hourly(5), beaufort, Central).unwrap();
+
+        assert_eq!(forecast.wind_speed[0], 2.0);
+        assert_eq!(forecast.wind_gust[0], Some(3.0));
         assert_eq!(
-            Forecast::get_value_and_time(&serde_json::json!({
-                "value": 81.15151,
-                "validTime": "2024-09-06T11:00:00+00:00/PT1H",
-            }))
-            .unwrap(),
-            (81.15151, "2024-09-06T11:00:00+00:00/PT1H")
-        )
+            forecast.wind_speed_description.as_deref().unwrap()[0],
+            "Light breeze"
+        );
+        assert_eq!(
+            forecast.wind_gust_description.as_deref().unwrap()[0],
+            Some("Gentle breeze")
+        );
+        assert_eq!(forecast.units.height, "ft");
     }
 }
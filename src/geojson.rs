@@ -0,0 +1,29 @@
+//! Minimal GeoJSON types, see https://datatracker.ietf.org/doc/html/rfc7946
+
+#[derive(serde::Serialize, Debug)]
+#[serde(tag = "type")]
+pub struct FeatureCollection<P> {
+    pub features: Vec<Feature<P>>,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(tag = "type")]
+pub struct Feature<P> {
+    pub geometry: Geometry,
+    pub properties: P,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    /// Position of the point as [longitude, latitude]
+    Point { coordinates: [f64; 2] },
+}
+
+impl<P> FromIterator<Feature<P>> for FeatureCollection<P> {
+    fn from_iter<I: IntoIterator<Item = Feature<P>>>(iter: I) -> Self {
+        Self {
+            features: iter.into_iter().collect(),
+        }
+    }
+}
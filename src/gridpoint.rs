@@ -0,0 +1,134 @@
+use crate::{Spot, Spots, nws_client, redis_utils};
+
+use anyhow::bail;
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
+use tracing::{info, warn};
+
+/// How long a resolved gridpoint is kept in Redis, one day.
+const GRIDPOINT_EXPIRY: u64 = 60 * 60 * 24;
+
+/// A cell in the NWS forecast grid.
+#[derive(Debug, PartialEq)]
+pub struct Gridpoint {
+    pub office: String,
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(serde::Deserialize)]
+struct PointsResponse {
+    properties: PointsProperties,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PointsProperties {
+    grid_id: String,
+    grid_x: u32,
+    grid_y: u32,
+}
+
+impl Gridpoint {
+    /// Path to the gridpoint's forecast data, e.g. "/gridpoints/MKX/90,67"
+    pub fn path(&self) -> String {
+        format!("/gridpoints/{}/{},{}", self.office, self.x, self.y)
+    }
+
+    /// Looks up the gridpoint covering the coordinates with the NWS points api.
+    pub async fn try_resolve(
+        latitude: f64,
+        longitude: f64,
+        forecast_url: &str,
+    ) -> anyhow::Result<Self> {
+        let response = nws_client()?
+            .get(format!(
+                "{forecast_url}{}",
+                points_path(latitude, longitude)
+            ))
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            bail!("NWS points api responded with {}", response.status());
+        }
+
+        let PointsResponse { properties } = response.json::<PointsResponse>().await?;
+
+        Ok(Self {
+            office: properties.grid_id,
+            x: properties.grid_x,
+            y: properties.grid_y,
+        })
+    }
+}
+
+/// Path to the NWS points api for the coordinates. NWS only accepts up to
+/// four decimal places.
+pub fn points_path(latitude: f64, longitude: f64) -> String {
+    format!("/points/{latitude:.4},{longitude:.4}")
+}
+
+fn cache_key(slug: &str) -> String {
+    format!("gridpoint-{slug}")
+}
+
+/// Gets the spot's gridpoint path cached by an earlier resolve.
+pub(crate) async fn cached_gridpoint(
+    spot: &Spot,
+    redis_pool: &Pool<RedisConnectionManager>,
+) -> Option<String> {
+    redis_utils::get(&cache_key(spot.location.slug), redis_pool).await
+}
+
+/// Caches the spot's resolved gridpoint path so it isn't resolved again on
+/// restart.
+pub(crate) async fn cache_gridpoint(
+    spot: &Spot,
+    path: &str,
+    redis_pool: &Pool<RedisConnectionManager>,
+) {
+    let key = cache_key(spot.location.slug);
+    redis_utils::cache_with_expiry(&key, path, GRIDPOINT_EXPIRY, redis_pool).await;
+}
+
+/// Resolves the gridpoint of every spot that doesn't already have one,
+/// preferring what's cached in Redis. Failures are only logged, the spot will
+/// try again when its forecast is requested.
+pub async fn resolve_gridpoints(
+    spots: Spots,
+    redis_pool: Pool<RedisConnectionManager>,
+    forecast_url: &'static str,
+) {
+    for spot in spots.iter().filter(|spot| !spot.has_forecast_path()) {
+        match spot.try_forecast_path(forecast_url, &redis_pool).await {
+            Ok(path) => info!("resolved {} to {path}", spot.name),
+            Err(e) => warn!("{e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_formats_the_gridpoint() {
+        let gridpoint = Gridpoint {
+            office: "MKX".to_string(),
+            x: 90,
+            y: 67,
+        };
+
+        assert_eq!(gridpoint.path(), "/gridpoints/MKX/90,67");
+    }
+
+    #[test]
+    fn points_path_limits_coordinates_to_four_decimals() {
+        assert_eq!(
+            points_path(43.089412345, -87.87),
+            "/points/43.0894,-87.8700"
+        );
+    }
+}
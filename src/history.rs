@@ -0,0 +1,255 @@
+use crate::{
+    AppState, Length, NdbcObservation, Realtime, Speed, Spot, Temperature, UnitLabels, Units,
+    observations_path, quality::round_to_tenth, spot::query_rejection, utils::redis_utils,
+};
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+    response::Response,
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use tracing::warn;
+
+/// Hours of history returned unless asked for otherwise.
+pub const DEFAULT_HISTORY_HOURS: u32 = 24;
+/// NDBC realtime files only go back 45 days.
+pub const MAX_HISTORY_HOURS: u32 = 45 * 24;
+
+/// A station's observations over the last few hours, oldest first.
+#[derive(serde::Serialize, Debug)]
+pub struct RealtimeHistory {
+    pub spot: &'static str,
+    pub station: &'static str,
+    pub hours: u32,
+    pub observations: Vec<HistoryObservation>,
+    /// The units the values are in
+    pub units: UnitLabels,
+}
+
+/// A single observation in the same units as the latest realtime data.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct HistoryObservation {
+    pub observed_at: String,
+    pub wind_direction: Option<f64>,
+    pub wind_speed: Option<f64>,
+    pub gusts: Option<f64>,
+    pub wave_height: Option<f64>,
+    pub wave_period: Option<f64>,
+    pub water_temp: Option<f64>,
+}
+
+/// The fields of an [`NdbcObservation`] the history shows, in canonical
+/// units. A station's whole file is cached as these so every request's hours
+/// and units are cut from the same parse.
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct SeriesObservation {
+    /// Unix timestamp, in seconds
+    observed_at: i64,
+    wind_direction: Option<f64>,
+    wind_speed: Option<f64>,
+    gust: Option<f64>,
+    wave_height: Option<f64>,
+    dominant_period: Option<f64>,
+    water_temp: Option<f64>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct HistoryParam {
+    pub hours: Option<u32>,
+}
+
+/// Extracts the hours of history from the `hours` query param, a day by
+/// default.
+pub struct HistoryQuery(pub u32);
+
+// Ensure api requests with bad query params return JSON rather than plain text.
+impl<S> FromRequestParts<S> for HistoryQuery
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Query::<HistoryParam>::from_request_parts(parts, state).await {
+            Ok(Query(param)) => Ok(HistoryQuery(param.hours.unwrap_or(DEFAULT_HISTORY_HOURS))),
+            Err(e) => Err(query_rejection(e)),
+        }
+    }
+}
+
+fn cache_key(station: &str) -> String {
+    format!("realtime-series-{station}")
+}
+
+impl RealtimeHistory {
+    /// Gets the history from the first of the spot's stations that loads.
+    pub async fn try_get(
+        spot: &Spot,
+        hours: u32,
+        state: &AppState,
+        units: Units,
+    ) -> anyhow::Result<Self> {
+        let hours = hours.clamp(1, MAX_HISTORY_HOURS);
+        let (station, series) = Self::try_get_series(spot, state).await?;
+
+        #[cfg(not(feature = "mock-time"))]
+        let now = Utc::now();
+        // When mocking time, the latest observation was just made
+        #[cfg(feature = "mock-time")]
+        let now = series
+            .first()
+            .and_then(|latest| DateTime::from_timestamp(latest.observed_at, 0))
+            .unwrap_or_else(Utc::now);
+
+        Ok(Self::new(
+            spot.location.slug,
+            station,
+            hours,
+            &series,
+            now,
+            units,
+        ))
+    }
+
+    /// Gets the observations of the first of the spot's stations that loads,
+    /// newest first, from the Redis cache when they're there.
+    async fn try_get_series(
+        spot: &Spot,
+        state: &AppState,
+    ) -> anyhow::Result<(&'static str, Vec<SeriesObservation>)> {
+        let mut last_error = None;
+
+        for &station in &spot.stations {
+            let cache_key = cache_key(station);
+            if let Some(series) = redis_utils::get(&cache_key, &state.redis_pool)
+                .await
+                .and_then(|data| serde_json::from_str(&data).ok())
+            {
+                tracing::info!("redis cache hit!");
+                return Ok((station, series));
+            }
+
+            let series = match Realtime::get_data(&observations_path(station), state.realtime_url)
+                .await
+                .and_then(|data| NdbcObservation::try_parse_all(&data))
+            {
+                Ok(observations) => observations
+                    .iter()
+                    .map(SeriesObservation::from)
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    warn!("Unable to load the history of station {station}: {e}");
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+
+            let data = serde_json::to_string(&series)?;
+            redis_utils::cache(&cache_key, &data, &state.redis_pool).await;
+
+            return Ok((station, series));
+        }
+
+        Err(last_error.unwrap_or(anyhow::anyhow!("{} has no stations", spot.name)))
+    }
+
+    fn new(
+        spot: &'static str,
+        station: &'static str,
+        hours: u32,
+        series: &[SeriesObservation],
+        now: DateTime<Utc>,
+        units: Units,
+    ) -> Self {
+        let since = (now - TimeDelta::hours(hours.into())).timestamp();
+
+        let mut observations = series
+            .iter()
+            .take_while(|observation| observation.observed_at >= since)
+            .filter_map(|observation| HistoryObservation::new(observation, units))
+            .collect::<Vec<_>>();
+        observations.reverse();
+
+        Self {
+            spot,
+            station,
+            hours,
+            observations,
+            units: units.labels(),
+        }
+    }
+}
+
+impl From<&NdbcObservation> for SeriesObservation {
+    fn from(observation: &NdbcObservation) -> Self {
+        Self {
+            observed_at: observation.observed_at.timestamp(),
+            wind_direction: observation.wind_direction,
+            wind_speed: observation.wind_speed,
+            gust: observation.gust,
+            wave_height: observation.wave_height,
+            dominant_period: observation.dominant_period,
+            water_temp: observation.water_temp,
+        }
+    }
+}
+
+impl HistoryObservation {
+    /// Converts the observation into the units, `None` when its timestamp is
+    /// out of range.
+    fn new(observation: &SeriesObservation, units: Units) -> Option<Self> {
+        let speed = |v| units.speed(Speed::from_meters_per_second(v)).round();
+
+        Some(Self {
+            observed_at: DateTime::from_timestamp(observation.observed_at, 0)?.to_rfc3339(),
+            wind_direction: observation.wind_direction,
+            wind_speed: observation.wind_speed.map(speed),
+            gusts: observation.gust.map(speed),
+            wave_height: observation
+                .wave_height
+                .map(|v| round_to_tenth(units.height(Length::from_meters(v)))),
+            wave_period: observation.dominant_period,
+            water_temp: observation
+                .water_temp
+                .map(|v| units.temperature(Temperature::from_celsius(v)).round()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_is_the_last_hours_oldest_first() {
+        let series =
+            NdbcObservation::try_parse_all(include_str!("../tests/fixtures/ndbc/45013.txt"))
+                .unwrap()
+                .iter()
+                .map(SeriesObservation::from)
+                .collect::<Vec<_>>();
+        let now = "2024-06-10T22:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let history = RealtimeHistory::new("atwater", "45013", 1, &series, now, Units::IMPERIAL);
+
+        assert_eq!(history.spot, "atwater");
+        assert_eq!(history.observations.len(), 6);
+        assert_eq!(
+            history.observations[0].observed_at,
+            "2024-06-10T21:00:00+00:00"
+        );
+        assert_eq!(
+            history.observations[5],
+            HistoryObservation {
+                observed_at: "2024-06-10T21:50:00+00:00".to_string(),
+                wind_direction: Some(30.0),
+                wind_speed: Some(11.0),
+                gusts: Some(16.0),
+                wave_height: Some(1.3),
+                wave_period: Some(4.0),
+                water_temp: Some(56.0),
+            }
+        );
+    }
+}
@@ -1,10 +1,21 @@
+mod compare;
 mod configuration;
 mod forecast;
+mod geojson;
+mod gridpoint;
+mod history;
+mod marine;
+mod ndbc;
+mod provider;
 mod quality;
 mod realtime;
 mod routes;
+mod session;
 mod spot;
+mod surf_zone;
+mod units;
 mod utils;
+mod water_level;
 mod water_quality;
 
 use axum::{Router, routing::get};
@@ -15,12 +26,26 @@ use tokio::sync::broadcast::Sender;
 use tower_http::services::ServeDir;
 use tower_http::trace::TraceLayer;
 
-pub use configuration::{Settings, get_configuration};
+pub use compare::*;
+pub use configuration::{Settings, SpotSettings, get_configuration};
 pub use forecast::*;
+pub use geojson::*;
+pub use gridpoint::*;
+pub use history::*;
+pub use marine::*;
+pub use ndbc::*;
+pub use provider::*;
 pub use quality::*;
-pub use realtime::Realtime;
+pub use realtime::{
+    CachedQuality, Realtime, RipCurrent, RipCurrentSource, Source, Sources, WaveComponent,
+    WaveComponents,
+};
+pub use session::*;
 pub use spot::*;
+pub use surf_zone::*;
+pub use units::*;
 pub use utils::*;
+pub use water_level::*;
 pub use water_quality::*;
 
 templates::init!();
@@ -28,10 +53,13 @@ templates::init!();
 #[derive(Clone)]
 pub struct AppState {
     redis_pool: Pool<RedisConnectionManager>,
+    spots: Spots,
     breaks: Vec<Location>,
     forecast_url: &'static str,
     realtime_url: &'static str,
     quality_url: &'static str,
+    water_level_url: &'static str,
+    glcfs_url: &'static str,
     #[cfg(debug_assertions)]
     event_stream: Sender<&'static str>,
 }
@@ -39,7 +67,8 @@ pub struct AppState {
 /// Function to startup the server
 ///
 /// # Panics
-/// Could panic if unable to find Redis instance to connect to.
+/// Could panic if unable to find Redis instance to connect to, or if the
+/// configured spots are invalid.
 ///
 pub async fn startup(settings: &'static Settings) -> (Option<Sender<&'static str>>, Router) {
     #[cfg(debug_assertions)]
@@ -57,23 +86,42 @@ pub async fn startup(settings: &'static Settings) -> (Option<Sender<&'static str
 
     let redis_pool = bb8::Pool::builder().build(redis_manager).await.unwrap();
 
+    let spots = Spots::try_new(&settings.spots).expect("Invalid spot configuration.");
+
     // Create an AppState that is shared across the app.
     let state = AppState {
         redis_pool,
-        breaks: Location::get_all(),
+        breaks: spots.get_all(),
+        spots,
         forecast_url: &settings.forecast_api.base_url,
         realtime_url: &settings.realtime_api.base_url,
         quality_url: &settings.quality_api.base_url,
+        water_level_url: &settings.water_level_api.base_url,
+        glcfs_url: &settings.glcfs_api.base_url,
         #[cfg(debug_assertions)]
         event_stream: tx.clone(),
     };
 
+    // Look up the NWS gridpoints without holding up the server starting.
+    tokio::spawn(resolve_gridpoints(
+        state.spots.clone(),
+        state.redis_pool.clone(),
+        state.forecast_url,
+    ));
+
     #[cfg(debug_assertions)]
     let watch_state = state.clone();
 
     let api = Router::new()
+        .route("/compare", get(routes::compare))
         .route("/realtime", get(routes::realtime))
-        .route("/forecast", get(routes::forecast));
+        .route("/realtime/history", get(routes::realtime_history))
+        .route("/forecast", get(routes::forecast))
+        .route("/forecast/sessions", get(routes::forecast_sessions))
+        .route("/water-level", get(routes::water_level))
+        .route("/spots", get(routes::spots))
+        .route("/spots.geojson", get(routes::spots_geojson))
+        .route("/spots/nearest", get(routes::nearest_spots));
 
     #[cfg(debug_assertions)]
     let tx = Some(tx);
@@ -85,6 +133,7 @@ pub async fn startup(settings: &'static Settings) -> (Option<Sender<&'static str
         // attaches the root route to the root.
         .route("/", get(routes::root))
         .route("/glimpse", get(routes::glimpse))
+        .route("/compare", get(routes::compare_page))
         .nest("/api", api)
         .fallback(routes::handle_404)
         // binds the telemetry.
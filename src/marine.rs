@@ -0,0 +1,129 @@
+use crate::{Spot, nws_client};
+
+use anyhow::bail;
+
+#[derive(serde::Deserialize)]
+struct ZoneForecastResponse {
+    properties: ZoneForecastProperties,
+}
+
+#[derive(serde::Deserialize)]
+struct ZoneForecastProperties {
+    updated: String,
+    periods: Vec<ZoneForecastPeriod>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ZoneForecastPeriod {
+    name: String,
+    detailed_forecast: String,
+}
+
+#[derive(serde::Deserialize)]
+struct AlertsResponse {
+    features: Vec<AlertFeature>,
+}
+
+#[derive(serde::Deserialize)]
+struct AlertFeature {
+    properties: Alert,
+}
+
+/// The NWS nearshore marine forecast text for a spot's zone.
+#[derive(serde::Serialize, Debug)]
+pub struct MarineForecast {
+    pub zone: &'static str,
+    pub updated: String,
+    pub periods: Vec<MarinePeriod>,
+}
+
+/// e.g. "Tonight", "North wind 5 to 10 kt. Waves 1 to 3 ft."
+#[derive(serde::Serialize, Debug)]
+pub struct MarinePeriod {
+    pub name: String,
+    pub forecast: String,
+}
+
+/// An active NWS hazard, e.g. a Small Craft Advisory or Beach Hazards
+/// Statement.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct Alert {
+    pub event: String,
+    pub headline: Option<String>,
+    /// "Extreme", "Severe", "Moderate", "Minor" or "Unknown"
+    pub severity: String,
+    pub description: String,
+    pub instruction: Option<String>,
+    pub onset: Option<String>,
+    pub ends: Option<String>,
+}
+
+impl MarineForecast {
+    /// Gets the text forecast for the spot's marine zone, `None` when the
+    /// spot doesn't have one.
+    pub async fn try_get(spot: &Spot, forecast_url: &str) -> anyhow::Result<Option<Self>> {
+        let Some(zone) = spot.marine_zone else {
+            return Ok(None);
+        };
+
+        let response = try_get_json::<ZoneForecastResponse>(&format!(
+            "{forecast_url}/zones/forecast/{zone}/forecast"
+        ))
+        .await?;
+
+        Ok(Some(Self {
+            zone,
+            updated: response.properties.updated,
+            periods: response
+                .properties
+                .periods
+                .into_iter()
+                .map(|period| MarinePeriod {
+                    name: period.name,
+                    forecast: period.detailed_forecast,
+                })
+                .collect(),
+        }))
+    }
+}
+
+impl Alert {
+    /// Gets the hazards active in the spot's marine and beach zones.
+    pub async fn try_get_active(spot: &Spot, forecast_url: &str) -> anyhow::Result<Vec<Self>> {
+        let zones = [spot.marine_zone, spot.beach_zone]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        if zones.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let response = try_get_json::<AlertsResponse>(&format!(
+            "{forecast_url}/alerts/active?zone={}",
+            zones.join(",")
+        ))
+        .await?;
+
+        Ok(response
+            .features
+            .into_iter()
+            .map(|feature| feature.properties)
+            .collect())
+    }
+}
+
+pub(crate) async fn try_get_json<T: serde::de::DeserializeOwned>(url: &str) -> anyhow::Result<T> {
+    let response = nws_client()?
+        .get(url)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if response.status().as_u16() != 200 {
+        bail!("Non 200 response from NOAA: {url}");
+    }
+
+    Ok(response.json().await?)
+}
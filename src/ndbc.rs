@@ -0,0 +1,576 @@
+use crate::{Kind, Unit};
+
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, TimeZone, Utc};
+use tracing::warn;
+
+/// Marker NDBC uses for a value a station didn't report.
+const MISSING: &str = "MM";
+/// Marker NDBC uses for a spectral value that can't be computed.
+const NOT_APPLICABLE: &str = "N/A";
+
+const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// Path of a station's standard meteorological file on the NDBC realtime api.
+pub fn observations_path(station: &str) -> String {
+    format!("/data/realtime2/{station}.txt")
+}
+
+/// Path of a bouy's spectral wave summary on the NDBC realtime api.
+pub fn spectral_summary_path(station: &str) -> String {
+    format!("/data/realtime2/{station}.spec")
+}
+
+/// A single row of an NDBC standard meteorological file, e.g.
+/// `/data/realtime2/45013.txt`. Values are converted from the units the file
+/// declares into the canonical unit of their [`Kind`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NdbcObservation {
+    pub observed_at: DateTime<Utc>,
+    /// WDIR, degrees the wind is coming from
+    pub wind_direction: Option<f64>,
+    /// WSPD
+    pub wind_speed: Option<f64>,
+    /// GST
+    pub gust: Option<f64>,
+    /// WVHT, significant wave height
+    pub wave_height: Option<f64>,
+    /// DPD, dominant wave period
+    pub dominant_period: Option<f64>,
+    /// APD, average wave period
+    pub average_period: Option<f64>,
+    /// MWD, degrees the dominant waves are coming from
+    pub wave_direction: Option<f64>,
+    /// PRES, hPa
+    pub pressure: Option<f64>,
+    /// ATMP
+    pub air_temp: Option<f64>,
+    /// WTMP
+    pub water_temp: Option<f64>,
+    /// DEWP
+    pub dewpoint: Option<f64>,
+}
+
+/// A single row of an NDBC spectral wave summary, e.g.
+/// `/data/realtime2/45013.spec`, splitting the waves into swell and wind
+/// waves. Values are converted like [`NdbcObservation`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NdbcSpectralSummary {
+    pub observed_at: DateTime<Utc>,
+    /// WVHT, significant wave height
+    pub wave_height: Option<f64>,
+    /// SwH, swell height
+    pub swell_height: Option<f64>,
+    /// SwP, swell period
+    pub swell_period: Option<f64>,
+    /// SwD, degrees the swell is coming from
+    pub swell_direction: Option<f64>,
+    /// WWH, wind wave height
+    pub wind_wave_height: Option<f64>,
+    /// WWP, wind wave period
+    pub wind_wave_period: Option<f64>,
+    /// WWD, degrees the wind waves are coming from
+    pub wind_wave_direction: Option<f64>,
+    /// STEEPNESS, e.g. "SWELL" or "VERY_STEEP"
+    pub steepness: Option<String>,
+    /// APD, average wave period
+    pub average_period: Option<f64>,
+    /// MWD, degrees the dominant waves are coming from
+    pub wave_direction: Option<f64>,
+}
+
+impl NdbcObservation {
+    /// Parses every observation in the file, newest first as NDBC lists them.
+    /// Columns are found by the header row so stations reporting a different
+    /// set of columns parse the same.
+    ///
+    /// Rows with a different number of columns than the header, a value that
+    /// isn't a number or `MM`, or an unknown unit are logged and skipped.
+    ///
+    /// # Errors
+    /// When the header is missing.
+    pub fn try_parse_all(data: &str) -> anyhow::Result<Vec<Self>> {
+        try_parse_all(Self::try_parse_rows(data))
+    }
+
+    /// Parses only the latest observation in the file, skipping newer rows
+    /// that don't parse.
+    ///
+    /// # Errors
+    /// As [`NdbcObservation::try_parse_all`], or when no row parses.
+    pub fn try_parse_latest(data: &str) -> anyhow::Result<Self> {
+        try_parse_latest(Self::try_parse_rows(data))
+    }
+
+    fn try_parse_rows(
+        data: &str,
+    ) -> anyhow::Result<impl Iterator<Item = anyhow::Result<Self>> + '_> {
+        try_parse_rows(data, |row| {
+            Ok(Self {
+                observed_at: row.observed_at()?,
+                wind_direction: row.number("WDIR")?,
+                wind_speed: row.quantity("WSPD", Kind::Speed)?,
+                gust: row.quantity("GST", Kind::Speed)?,
+                wave_height: row.quantity("WVHT", Kind::Length)?,
+                dominant_period: row.quantity("DPD", Kind::Duration)?,
+                average_period: row.quantity("APD", Kind::Duration)?,
+                wave_direction: row.number("MWD")?,
+                pressure: row.number("PRES")?,
+                air_temp: row.quantity("ATMP", Kind::Temperature)?,
+                water_temp: row.quantity("WTMP", Kind::Temperature)?,
+                dewpoint: row.quantity("DEWP", Kind::Temperature)?,
+            })
+        })
+    }
+}
+
+impl NdbcSpectralSummary {
+    /// Parses every summary in the `.spec` file, newest first.
+    ///
+    /// Rows are skipped as in [`NdbcObservation::try_parse_all`], including
+    /// when a direction isn't a compass point.
+    ///
+    /// # Errors
+    /// When the header is missing.
+    pub fn try_parse_all(data: &str) -> anyhow::Result<Vec<Self>> {
+        try_parse_all(Self::try_parse_rows(data))
+    }
+
+    /// Parses only the latest summary in the file, skipping newer rows that
+    /// don't parse.
+    ///
+    /// # Errors
+    /// As [`NdbcSpectralSummary::try_parse_all`], or when no row parses.
+    pub fn try_parse_latest(data: &str) -> anyhow::Result<Self> {
+        try_parse_latest(Self::try_parse_rows(data))
+    }
+
+    fn try_parse_rows(
+        data: &str,
+    ) -> anyhow::Result<impl Iterator<Item = anyhow::Result<Self>> + '_> {
+        try_parse_rows(data, |row| {
+            Ok(Self {
+                observed_at: row.observed_at()?,
+                wave_height: row.quantity("WVHT", Kind::Length)?,
+                swell_height: row.quantity("SwH", Kind::Length)?,
+                swell_period: row.quantity("SwP", Kind::Duration)?,
+                swell_direction: row.compass_point("SwD")?,
+                wind_wave_height: row.quantity("WWH", Kind::Length)?,
+                wind_wave_period: row.quantity("WWP", Kind::Duration)?,
+                wind_wave_direction: row.compass_point("WWD")?,
+                steepness: row.text("STEEPNESS").map(str::to_string),
+                average_period: row.quantity("APD", Kind::Duration)?,
+                wave_direction: row.number("MWD")?,
+            })
+        })
+    }
+}
+
+/// Parses each row of an NDBC realtime file with the columns named by its
+/// header row and the units declared by the units row that follows it.
+///
+/// # Errors
+/// When the header is missing. Each row carries its own error so one bad row
+/// doesn't lose the rest of the file.
+fn try_parse_rows<'a, T>(
+    data: &'a str,
+    parse: impl Fn(&Row) -> anyhow::Result<T> + 'a,
+) -> anyhow::Result<impl Iterator<Item = anyhow::Result<T>> + 'a> {
+    let mut lines = data.lines();
+
+    let header = lines
+        .next()
+        .and_then(|line| line.strip_prefix('#'))
+        .ok_or(anyhow!("NDBC data is missing its header row"))?
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    let units = data
+        .lines()
+        .nth(1)
+        .and_then(|line| line.strip_prefix('#'))
+        .map(|line| line.split_whitespace().collect::<Vec<_>>());
+
+    Ok(lines
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .map(move |line| {
+            let values = line.split_whitespace().collect::<Vec<_>>();
+
+            if values.len() != header.len() {
+                bail!(
+                    "NDBC row has {} columns, expected {}: {line}",
+                    values.len(),
+                    header.len()
+                );
+            }
+
+            parse(&Row {
+                header: &header,
+                units: units.as_deref(),
+                values,
+                line,
+            })
+        }))
+}
+
+/// Every row that parses, warning about and skipping the rest.
+fn try_parse_all<T>(
+    rows: anyhow::Result<impl Iterator<Item = anyhow::Result<T>>>,
+) -> anyhow::Result<Vec<T>> {
+    Ok(rows?
+        .filter_map(|row| row.inspect_err(|e| warn!("Skipping NDBC row: {e}")).ok())
+        .collect())
+}
+
+/// The first row that parses, without parsing the older rows after it.
+fn try_parse_latest<T>(
+    rows: anyhow::Result<impl Iterator<Item = anyhow::Result<T>>>,
+) -> anyhow::Result<T> {
+    let mut first_error = None;
+
+    for row in rows? {
+        match row {
+            Ok(row) => return Ok(row),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    Err(first_error.unwrap_or(anyhow!("NDBC data has no observations")))
+}
+
+/// The values of a row, looked up by their column in the header.
+struct Row<'a> {
+    header: &'a [&'a str],
+    units: Option<&'a [&'a str]>,
+    values: Vec<&'a str>,
+    line: &'a str,
+}
+
+impl Row<'_> {
+    /// The value of the column, `None` when the station doesn't report it or
+    /// it's missing.
+    fn text(&self, name: &str) -> Option<&str> {
+        let column = self.header.iter().position(|h| *h == name)?;
+
+        match self.values[column] {
+            MISSING | NOT_APPLICABLE => None,
+            value => Some(value),
+        }
+    }
+
+    fn number(&self, name: &str) -> anyhow::Result<Option<f64>> {
+        self.text(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| anyhow!("NDBC {name} value '{value}' isn't a number"))
+            })
+            .transpose()
+    }
+
+    /// The value of the column converted from its declared unit into the
+    /// canonical unit of `kind`.
+    fn quantity(&self, name: &str, kind: Kind) -> anyhow::Result<Option<f64>> {
+        let Some(value) = self.number(name)? else {
+            return Ok(None);
+        };
+
+        let unit = self
+            .header
+            .iter()
+            .position(|h| *h == name)
+            .and_then(|column| self.units?.get(column))
+            .ok_or(anyhow!("NDBC data has no unit for {name}"))?
+            .parse::<Unit>()
+            .map_err(|e| anyhow!("NDBC {name}: {e}"))?;
+
+        unit.try_to_canonical(value, kind)
+            .map(Some)
+            .map_err(|e| anyhow!("NDBC {name}: {e}"))
+    }
+
+    /// Degrees of a compass point column, e.g. "NNE" is 22.5.
+    fn compass_point(&self, name: &str) -> anyhow::Result<Option<f64>> {
+        self.text(name)
+            .map(|value| {
+                COMPASS_POINTS
+                    .iter()
+                    .position(|point| *point == value)
+                    .map(|index| index as f64 * 22.5)
+                    .ok_or(anyhow!("NDBC {name} value '{value}' isn't a compass point"))
+            })
+            .transpose()
+    }
+
+    fn observed_at(&self) -> anyhow::Result<DateTime<Utc>> {
+        let time_part = |name: &str| -> anyhow::Result<u32> {
+            self.number(name)?
+                .map(|v| v as u32)
+                .ok_or(anyhow!("NDBC row is missing {name}: {}", self.line))
+        };
+
+        Utc.with_ymd_and_hms(
+            time_part("YY")? as i32,
+            time_part("MM")?,
+            time_part("DD")?,
+            time_part("hh")?,
+            // Older files don't report minutes
+            self.number("mm")?.map_or(0, |v| v as u32),
+            0,
+        )
+        .single()
+        .ok_or(anyhow!("NDBC row has an invalid time: {}", self.line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Speed;
+
+    struct Case {
+        station: &'static str,
+        data: &'static str,
+        observations: usize,
+        latest: NdbcObservation,
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        time.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_station_files() {
+        let cases = [
+            Case {
+                station: "45013, Atwater bouy",
+                data: include_str!("../tests/fixtures/ndbc/45013.txt"),
+                observations: 8,
+                latest: NdbcObservation {
+                    observed_at: at("2024-06-10T21:50:00Z"),
+                    wind_direction: Some(30.0),
+                    wind_speed: Some(5.0),
+                    gust: Some(7.0),
+                    wave_height: Some(0.4),
+                    dominant_period: Some(4.0),
+                    average_period: Some(3.3),
+                    wave_direction: None,
+                    pressure: Some(1015.2),
+                    air_temp: Some(15.1),
+                    water_temp: Some(13.4),
+                    dewpoint: None,
+                },
+            },
+            Case {
+                station: "45007, mid lake bouy",
+                data: include_str!("../tests/fixtures/ndbc/45007.txt"),
+                observations: 6,
+                latest: NdbcObservation {
+                    observed_at: at("2024-06-10T21:40:00Z"),
+                    wind_direction: Some(20.0),
+                    wind_speed: Some(6.0),
+                    gust: Some(8.0),
+                    wave_height: Some(0.7),
+                    dominant_period: Some(5.0),
+                    average_period: Some(3.9),
+                    wave_direction: Some(15.0),
+                    pressure: Some(1016.0),
+                    air_temp: Some(12.4),
+                    water_temp: Some(10.2),
+                    dewpoint: Some(8.1),
+                },
+            },
+            Case {
+                station: "MLWW3, Milwaukee land station",
+                data: include_str!("../tests/fixtures/ndbc/MLWW3.txt"),
+                observations: 6,
+                latest: NdbcObservation {
+                    observed_at: at("2024-06-10T22:00:00Z"),
+                    wind_direction: Some(40.0),
+                    wind_speed: Some(4.6),
+                    gust: Some(6.7),
+                    wave_height: None,
+                    dominant_period: None,
+                    average_period: None,
+                    wave_direction: None,
+                    pressure: Some(1015.3),
+                    air_temp: Some(16.8),
+                    water_temp: None,
+                    dewpoint: Some(9.4),
+                },
+            },
+        ];
+
+        for case in cases {
+            let observations = NdbcObservation::try_parse_all(case.data)
+                .unwrap_or_else(|e| panic!("{}: {e}", case.station));
+
+            assert_eq!(observations.len(), case.observations, "{}", case.station);
+            assert_eq!(observations[0], case.latest, "{}", case.station);
+        }
+    }
+
+    #[test]
+    fn skips_rows_that_dont_parse() {
+        let data = include_str!("../tests/fixtures/ndbc/45007-truncated.txt");
+
+        let observations = NdbcObservation::try_parse_all(data).unwrap();
+
+        assert_eq!(observations.len(), 4);
+        assert_eq!(observations[3].observed_at, at("2024-06-10T16:40:00Z"));
+    }
+
+    #[test]
+    fn the_latest_row_doesnt_need_older_rows_to_parse() {
+        let data = include_str!("../tests/fixtures/ndbc/45007-truncated.txt");
+
+        let latest = NdbcObservation::try_parse_latest(data).unwrap();
+
+        assert_eq!(latest.observed_at, at("2024-06-10T21:40:00Z"));
+    }
+
+    #[test]
+    fn the_latest_row_is_the_newest_that_parses() {
+        let data = "#YY  MM DD hh mm WSPD\n#yr  mo dy hr mn m/s\n2024 06 10 21 50 fast\n2024 06 10 21 40 5.0\n";
+
+        let latest = NdbcObservation::try_parse_latest(data).unwrap();
+
+        assert_eq!(latest.observed_at, at("2024-06-10T21:40:00Z"));
+        assert_eq!(latest.wind_speed, Some(5.0));
+    }
+
+    #[test]
+    fn parses_columns_by_the_header() {
+        let data =
+            "#YY  MM DD hh mm WTMP WDIR\n#yr  mo dy hr mn degC degT\n2024 06 10 21 50 13.4 MM\n";
+
+        let latest = NdbcObservation::try_parse_latest(data).unwrap();
+
+        assert_eq!(latest.water_temp, Some(13.4));
+        assert_eq!(latest.wind_direction, None);
+        assert_eq!(latest.wave_height, None);
+    }
+
+    #[test]
+    fn converts_the_declared_units() {
+        let data = "#YY  MM DD hh mm WSPD WTMP WVHT\n#yr  mo dy hr mn kts degF ft\n2024 06 10 21 50 10 50 2\n";
+
+        let latest = NdbcObservation::try_parse_latest(data).unwrap();
+
+        assert_eq!(
+            latest.wind_speed,
+            Some(Speed::from_knots(10.0).meters_per_second())
+        );
+        assert_eq!(latest.water_temp, Some(10.0));
+        assert_eq!(latest.wave_height, Some(0.6096));
+    }
+
+    #[test]
+    fn errors_rather_than_panics_on_bad_data() {
+        let header = "#YY  MM DD hh mm WDIR WSPD\n#yr  mo dy hr mn degT m/s\n";
+
+        let cases = [
+            ("", "header"),
+            ("2024 06 10 21 50 30 5.0\n", "header"),
+            (&*format!("{header}2024 06 10 21 50 30\n"), "columns"),
+            (&*format!("{header}2024 06 10 21 50 30 fast\n"), "WSPD"),
+            (
+                &*format!("{header}2024 13 10 21 50 30 5.0\n"),
+                "invalid time",
+            ),
+            (header, "no observations"),
+            (
+                "#YY  MM DD hh mm WDIR WSPD\n2024 06 10 21 50 30 5.0\n",
+                "no unit for WSPD",
+            ),
+            (
+                "#YY  MM DD hh mm WDIR WSPD\n#yr  mo dy hr mn degT furlongs\n2024 06 10 21 50 30 5.0\n",
+                "Unknown unit of measure furlongs",
+            ),
+        ];
+
+        for (data, expected) in cases {
+            let error = NdbcObservation::try_parse_latest(data)
+                .unwrap_err()
+                .to_string();
+
+            assert!(error.contains(expected), "{data:?}: {error}");
+        }
+    }
+
+    #[test]
+    fn parses_spectral_summaries() {
+        let cases = [
+            (
+                "45013, Atwater bouy",
+                include_str!("../tests/fixtures/ndbc/45013.spec"),
+                4,
+                NdbcSpectralSummary {
+                    observed_at: at("2024-06-10T21:50:00Z"),
+                    wave_height: Some(0.4),
+                    swell_height: Some(0.1),
+                    swell_period: Some(7.7),
+                    swell_direction: Some(90.0),
+                    wind_wave_height: Some(0.4),
+                    wind_wave_period: Some(4.0),
+                    wind_wave_direction: Some(22.5),
+                    steepness: Some("VERY_STEEP".to_string()),
+                    average_period: Some(3.3),
+                    wave_direction: None,
+                },
+            ),
+            (
+                "45007, mid lake bouy",
+                include_str!("../tests/fixtures/ndbc/45007.spec"),
+                3,
+                NdbcSpectralSummary {
+                    observed_at: at("2024-06-10T21:40:00Z"),
+                    wave_height: Some(0.7),
+                    swell_height: Some(0.5),
+                    swell_period: Some(8.3),
+                    swell_direction: Some(337.5),
+                    wind_wave_height: Some(0.4),
+                    wind_wave_period: Some(4.5),
+                    wind_wave_direction: Some(22.5),
+                    steepness: Some("AVERAGE".to_string()),
+                    average_period: Some(3.9),
+                    wave_direction: Some(15.0),
+                },
+            ),
+        ];
+
+        for (station, data, count, latest) in cases {
+            let summaries = NdbcSpectralSummary::try_parse_all(data)
+                .unwrap_or_else(|e| panic!("{station}: {e}"));
+
+            assert_eq!(summaries.len(), count, "{station}");
+            assert_eq!(summaries[0], latest, "{station}");
+        }
+    }
+
+    #[test]
+    fn spectral_values_that_cant_be_computed_are_missing() {
+        let summaries =
+            NdbcSpectralSummary::try_parse_all(include_str!("../tests/fixtures/ndbc/45007.spec"))
+                .unwrap();
+
+        assert_eq!(summaries[2].swell_height, None);
+        assert_eq!(summaries[2].swell_direction, None);
+        assert_eq!(summaries[2].steepness, None);
+    }
+
+    #[test]
+    fn spectral_directions_must_be_compass_points() {
+        let data = "#YY  MM DD hh mm SwD\n2024 06 10 21 50 NORTH\n";
+
+        let error = NdbcSpectralSummary::try_parse_latest(data)
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("compass point"), "{error}");
+    }
+}
@@ -0,0 +1,204 @@
+use super::{ForecastProvider, HourlyForecast};
+use crate::{Kind, Spot, Unit, nws_client};
+
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+
+/// Path of the ERDDAP griddap api serving the GLCFS model output.
+pub const GLCFS_PATH: &str = "/erddap/griddap";
+/// Model variables of the significant wave height, in meters, the wave period,
+/// in seconds, and the direction, in degrees, waves come from.
+const VARIABLES: [&str; 3] = ["wvh", "wvp", "wvd"];
+
+#[derive(serde::Deserialize)]
+struct ErddapResponse {
+    table: ErddapTable,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErddapTable {
+    column_names: Vec<String>,
+    /// e.g. "m", "s" and "degrees"
+    column_units: Vec<String>,
+    /// Values are null at grid cells over land and hours the model hasn't run
+    rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// The Great Lakes Coastal Forecasting System wave model, at the grid cell
+/// nearest a spot.
+pub struct Glcfs<'a> {
+    pub glcfs_url: &'a str,
+}
+
+impl ForecastProvider for Glcfs<'_> {
+    fn name(&self) -> &'static str {
+        "GLCFS"
+    }
+
+    /// Gets the waves from the start of the hour until the end of the model's
+    /// run, `None` when the spot doesn't have a GLCFS dataset.
+    async fn try_get_hourly(&self, spot: &Spot) -> anyhow::Result<Option<HourlyForecast>> {
+        let Some(dataset) = spot.glcfs_dataset else {
+            return Ok(None);
+        };
+
+        // ERDDAP picks the grid cell nearest to the coordinates
+        let starting_at = Utc::now().duration_trunc(TimeDelta::hours(1))?;
+        let constraints = format!(
+            "[({}):1:(last)][({})][({})]",
+            starting_at.format("%Y-%m-%dT%H:%M:%SZ"),
+            spot.location.latitude,
+            spot.location.longitude
+        );
+        let query = VARIABLES
+            .iter()
+            .map(|variable| format!("{variable}{constraints}"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let response = nws_client()?
+            .get(format!(
+                "{}{GLCFS_PATH}/{dataset}.json?{query}",
+                self.glcfs_url
+            ))
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            bail!("Non 200 response from GLCFS");
+        }
+
+        let forecast = Self::try_parse(&response.text().await?)?;
+
+        // The nearest grid cell is over land, or the model's first hour is null.
+        if forecast.wave_height.is_empty() {
+            bail!(
+                "GLCFS {dataset} has no waves at {}, {}",
+                spot.location.latitude,
+                spot.location.longitude
+            );
+        }
+
+        Ok(Some(forecast))
+    }
+}
+
+impl Glcfs<'_> {
+    /// Parses the model's hours up to the first it has no waves for.
+    fn try_parse(data: &str) -> anyhow::Result<HourlyForecast> {
+        let table = serde_json::from_str::<ErddapResponse>(data)?.table;
+
+        let column = |name: &str| {
+            table
+                .column_names
+                .iter()
+                .position(|column| column == name)
+                .ok_or(anyhow!("GLCFS output has no {name} column"))
+        };
+        let variable = |name: &str| -> anyhow::Result<(usize, Unit)> {
+            let column = column(name)?;
+            let unit = table
+                .column_units
+                .get(column)
+                .ok_or(anyhow!("GLCFS output has no unit for {name}"))?
+                .parse()?;
+
+            Ok((column, unit))
+        };
+        let time = column("time")?;
+        let [wave_height, wave_period, wave_direction] = [
+            variable(VARIABLES[0])?,
+            variable(VARIABLES[1])?,
+            variable(VARIABLES[2])?,
+        ];
+
+        let time_of = |row: &[serde_json::Value]| -> anyhow::Result<DateTime<Utc>> {
+            let time = row
+                .get(time)
+                .and_then(serde_json::Value::as_str)
+                .ok_or(anyhow!("GLCFS output has a row without a time"))?;
+
+            Ok(DateTime::parse_from_rfc3339(time)?.with_timezone(&Utc))
+        };
+
+        let first = table
+            .rows
+            .first()
+            .ok_or(anyhow!("GLCFS output has no rows"))?;
+        let starting_at = time_of(first)?;
+
+        // ERDDAP doesn't report when the model ran, only the hours it covers.
+        let mut forecast = HourlyForecast {
+            starting_at,
+            ..Default::default()
+        };
+        for row in &table.rows {
+            let value = |(index, unit): (usize, Unit), kind: Kind| {
+                row.get(index)
+                    .and_then(serde_json::Value::as_f64)
+                    .map(|v| unit.try_to_canonical(v, kind))
+                    .transpose()
+            };
+            let (Some(height), Some(period), Some(direction)) = (
+                value(wave_height, Kind::Length)?,
+                value(wave_period, Kind::Duration)?,
+                value(wave_direction, Kind::Angle)?,
+            ) else {
+                break;
+            };
+
+            forecast.wave_height.push(height);
+            forecast.wave_period.push(Some(period));
+            forecast.wave_direction.push(Some(direction));
+        }
+
+        Ok(forecast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_waves_up_to_the_first_missing_hour() {
+        let forecast =
+            Glcfs::try_parse(include_str!("../../tests/fixtures/glcfs/atwater.json")).unwrap();
+
+        assert_eq!(
+            forecast.starting_at.to_rfc3339(),
+            "2024-06-10T20:00:00+00:00"
+        );
+        assert_eq!(forecast.updated_at, None);
+        assert_eq!(forecast.wave_height, [0.44, 0.54, 0.7]);
+        assert_eq!(forecast.wave_period, [Some(3.2), Some(3.6), Some(4.1)]);
+        assert_eq!(forecast.wave_direction[0], Some(40.0));
+        assert!(forecast.wind_speed.is_empty());
+    }
+
+    #[test]
+    fn a_grid_cell_over_land_has_no_waves() {
+        let forecast = Glcfs::try_parse(
+            r#"{"table": {
+                "columnNames": ["time", "wvh", "wvp", "wvd"],
+                "columnUnits": ["UTC", "m", "s", "degrees"],
+                "rows": [["2024-06-10T20:00:00Z", null, null, null]]
+            }}"#,
+        )
+        .unwrap();
+
+        assert!(forecast.wave_height.is_empty());
+    }
+
+    #[test]
+    fn output_missing_a_variable_is_an_error() {
+        let error = Glcfs::try_parse(
+            r#"{"table": {"columnNames": ["time", "wvh"], "columnUnits": ["UTC", "m"], "rows": []}}"#,
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("no wvp column"));
+    }
+}
@@ -0,0 +1,46 @@
+mod glcfs;
+mod nws;
+
+pub use glcfs::*;
+pub use nws::*;
+
+use crate::Spot;
+
+use chrono::{DateTime, Utc};
+
+/// A forecast normalized to a value per hour from `starting_at`, in the
+/// canonical unit of each [`Kind`](crate::Kind): heights in metres, speeds in
+/// m/s, temperatures in °C, periods in seconds, directions in degrees and
+/// probabilities and cover in percent. Series a provider doesn't forecast are
+/// empty. The quality is rated from the wave height and wind, the other series
+/// are `None` for hours the provider has no value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HourlyForecast {
+    /// When the model run was issued, if the provider reports it
+    pub updated_at: Option<DateTime<Utc>>,
+    pub starting_at: DateTime<Utc>,
+    pub wave_height: Vec<f64>,
+    pub wave_period: Vec<Option<f64>>,
+    /// Where the waves come from
+    pub wave_direction: Vec<Option<f64>>,
+    pub wind_speed: Vec<f64>,
+    pub wind_gust: Vec<Option<f64>>,
+    pub wind_direction: Vec<f64>,
+    pub temperature: Vec<Option<f64>>,
+    pub dewpoint: Vec<Option<f64>>,
+    pub probability_of_precipitation: Vec<Option<f64>>,
+    pub probability_of_thunder: Vec<Option<f64>>,
+    pub cloud_cover: Vec<Option<f64>>,
+}
+
+/// A source of forecasts, e.g. the NWS gridpoints or a wave model.
+pub trait ForecastProvider {
+    /// e.g. "NWS"
+    fn name(&self) -> &'static str;
+
+    /// Gets the spot's forecast, `None` when the provider doesn't cover it.
+    fn try_get_hourly(
+        &self,
+        spot: &Spot,
+    ) -> impl Future<Output = anyhow::Result<Option<HourlyForecast>>> + Send;
+}
@@ -0,0 +1,396 @@
+use super::{ForecastProvider, HourlyForecast};
+use crate::{
+    Kind, Spot, Unit,
+    utils::{nws_client, parse_duration},
+};
+
+use anyhow::{anyhow, bail};
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::Response;
+use tracing::{error, info, warn};
+
+#[derive(serde::Deserialize)]
+struct GridpointResponse {
+    properties: GridpointProperties,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GridpointProperties {
+    /// RFC 3339, e.g. "2024-09-06T11:54:57+00:00"
+    update_time: String,
+    /// The interval the layers cover, e.g. "2024-09-06T11:00:00+00:00/P7DT14H"
+    valid_times: String,
+    wave_height: Layer,
+    wave_period: Layer,
+    wave_direction: Layer,
+    wind_speed: Layer,
+    wind_gust: Layer,
+    wind_direction: Layer,
+    temperature: Layer,
+    dewpoint: Layer,
+    probability_of_precipitation: Layer,
+    probability_of_thunder: Layer,
+    sky_cover: Layer,
+}
+
+/// A gridpoint forecast layer, e.g. `waveHeight`.
+#[derive(serde::Deserialize)]
+struct Layer {
+    /// The layer's unit of measure, e.g. "wmoUnit:km_h-1"
+    uom: String,
+    values: Vec<LayerValue>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LayerValue {
+    /// e.g. "2024-09-06T11:00:00+00:00/PT2H"
+    valid_time: String,
+    /// Null where forecasters haven't filled in the grid
+    value: Option<f64>,
+}
+
+/// The NWS gridpoint forecast from api.weather.gov.
+pub struct Nws<'a> {
+    pub forecast_url: &'a str,
+    pub redis_pool: &'a Pool<RedisConnectionManager>,
+}
+
+impl ForecastProvider for Nws<'_> {
+    fn name(&self) -> &'static str {
+        "NWS"
+    }
+
+    async fn try_get_hourly(&self, spot: &Spot) -> anyhow::Result<Option<HourlyForecast>> {
+        let forecast_path = spot
+            .try_forecast_path(self.forecast_url, self.redis_pool)
+            .await?;
+        let data = Self::fetch_data(forecast_path, self.forecast_url).await?;
+
+        Self::try_parse(&data.text().await?).map(Some)
+    }
+}
+
+impl Nws<'_> {
+    async fn fetch_data(forecast_path: &str, forecast_url: &str) -> anyhow::Result<Response> {
+        let client = nws_client()?;
+
+        const RETRY: u8 = 2;
+        for _ in 0..RETRY {
+            let response = client
+                .get(format!("{forecast_url}{forecast_path}"))
+                .timeout(std::time::Duration::from_secs(10))
+                .send()
+                .await?;
+            if response.status().as_u16() == 200 {
+                info!("NOAA 200 success.");
+                return Ok(response);
+            }
+            warn!("NOAA non-200, retrying: {}{}", forecast_url, forecast_path);
+        }
+
+        error!("Non 200 response from NOAA");
+        bail!("Non 200 response from NOAA");
+    }
+
+    fn try_parse(data: &str) -> anyhow::Result<HourlyForecast> {
+        let properties = serde_json::from_str::<GridpointResponse>(data)
+            .map_err(|e| anyhow!("Invalid NWS gridpoint: {e}"))?
+            .properties;
+
+        let updated_at = properties
+            .update_time
+            .parse::<DateTime<Utc>>()
+            .map_err(|e| anyhow!("Invalid updateTime {}: {e}", properties.update_time))?;
+        let (starting_at, _) = parse_valid_time(&properties.valid_times)
+            .map_err(|e| anyhow!("Invalid validTimes: {e}"))?;
+
+        let layer = |name: &str, layer: &Layer, kind: Kind| {
+            layer
+                .try_expand(starting_at, kind)
+                .map_err(|e| anyhow!("Invalid {name} layer: {e}"))
+        };
+        // The quality can't be rated without these, the forecast starts at the
+        // first hour they all have a value.
+        let wave_height = layer("waveHeight", &properties.wave_height, Kind::Length)?;
+        let wind_speed = layer("windSpeed", &properties.wind_speed, Kind::Speed)?;
+        let wind_direction = layer("windDirection", &properties.wind_direction, Kind::Angle)?;
+
+        let first = |name: &str, values: &[Option<f64>]| {
+            values
+                .iter()
+                .position(Option::is_some)
+                .ok_or(anyhow!("Invalid {name} layer: no values"))
+        };
+        let skip = first("waveHeight", &wave_height)?
+            .max(first("windSpeed", &wind_speed)?)
+            .max(first("windDirection", &wind_direction)?);
+
+        // Filled forward, every hour from the first value has one
+        let required =
+            |values: Vec<Option<f64>>| values.into_iter().skip(skip).flatten().collect::<Vec<_>>();
+        let wave_height = required(wave_height);
+        let wind_speed = required(wind_speed);
+        let wind_direction = required(wind_direction);
+
+        // Layers that are empty or all null are all `None` for the forecast's hours.
+        let hours = wave_height.len();
+        let optional = |name: &str, values: &Layer, kind: Kind| {
+            let mut values = layer(name, values, kind)?
+                .into_iter()
+                .skip(skip)
+                .collect::<Vec<_>>();
+            if values.len() < hours {
+                values.resize(hours, None);
+            }
+
+            anyhow::Ok(values)
+        };
+
+        Ok(HourlyForecast {
+            updated_at: Some(updated_at),
+            starting_at: starting_at + TimeDelta::hours(skip as i64),
+            wave_height,
+            wave_period: optional("wavePeriod", &properties.wave_period, Kind::Duration)?,
+            wave_direction: optional("waveDirection", &properties.wave_direction, Kind::Angle)?,
+            wind_speed,
+            wind_gust: optional("windGust", &properties.wind_gust, Kind::Speed)?,
+            wind_direction,
+            temperature: optional("temperature", &properties.temperature, Kind::Temperature)?,
+            dewpoint: optional("dewpoint", &properties.dewpoint, Kind::Temperature)?,
+            probability_of_precipitation: optional(
+                "probabilityOfPrecipitation",
+                &properties.probability_of_precipitation,
+                Kind::Percent,
+            )?,
+            probability_of_thunder: optional(
+                "probabilityOfThunder",
+                &properties.probability_of_thunder,
+                Kind::Percent,
+            )?,
+            cloud_cover: optional("skyCover", &properties.sky_cover, Kind::Percent)?,
+        })
+    }
+}
+
+impl Layer {
+    /// Expands the values, each valid for a period, into a value for each hour
+    /// from `starting_at` in the canonical unit of `kind`. Hours without a
+    /// value take the previous hour's, the NWS leaves gaps where forecasters
+    /// haven't filled in the grid. Hours before the first value are `None`.
+    fn try_expand(
+        &self,
+        starting_at: DateTime<Utc>,
+        kind: Kind,
+    ) -> anyhow::Result<Vec<Option<f64>>> {
+        let unit = self.uom.parse::<Unit>()?;
+        let mut hours = Vec::<Option<f64>>::new();
+
+        for value in &self.values {
+            let (valid_at, duration) = parse_valid_time(&value.valid_time)?;
+            let offset = (valid_at - starting_at).num_hours();
+
+            for hour in offset..offset + duration.num_hours() {
+                // Hours before the forecast starts are dropped
+                let Ok(hour) = usize::try_from(hour) else {
+                    continue;
+                };
+                if hours.len() <= hour {
+                    hours.resize(hour + 1, None);
+                }
+                hours[hour] = value.value;
+            }
+        }
+
+        let mut last = None;
+
+        hours
+            .into_iter()
+            .map(|value| {
+                last = value.or(last);
+                last.map(|v| unit.try_to_canonical(v, kind)).transpose()
+            })
+            .collect()
+    }
+}
+
+/// Parses an ISO 8601 interval of a start time and a duration,
+/// e.g. "2024-07-18T05:00:00+00:00/P1DT5H".
+fn parse_valid_time(valid_time: &str) -> anyhow::Result<(DateTime<Utc>, TimeDelta)> {
+    let (start, duration) = valid_time
+        .split_once('/')
+        .ok_or(anyhow!("{valid_time} isn't an interval"))?;
+
+    Ok((
+        DateTime::parse_from_rfc3339(start)
+            .map_err(|e| anyhow!("{valid_time} has an invalid start: {e}"))?
+            .with_timezone(&Utc),
+        parse_duration(duration)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(uom: &str, values: serde_json::Value) -> Layer {
+        serde_json::from_value(serde_json::json!({ "uom": uom, "values": values })).unwrap()
+    }
+
+    fn starting_at() -> DateTime<Utc> {
+        "2024-09-06T11:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn layers_expand_into_hours_from_the_start() {
+        let hours = layer(
+            "wmoUnit:km_h-1",
+            serde_json::json!([
+                { "validTime": "2024-09-06T10:00:00+00:00/PT2H", "value": 36.0 },
+                { "validTime": "2024-09-06T12:00:00+00:00/P1DT1H", "value": 72.0 },
+            ]),
+        )
+        .try_expand(starting_at(), Kind::Speed)
+        .unwrap();
+
+        assert_eq!(hours.len(), 26);
+        assert_eq!(hours[..2], [Some(10.0), Some(20.0)]);
+    }
+
+    #[test]
+    fn hours_without_a_value_take_the_previous_hours() {
+        let hours = layer(
+            "wmoUnit:m",
+            serde_json::json!([
+                { "validTime": "2024-09-06T11:00:00+00:00/PT1H", "value": null },
+                { "validTime": "2024-09-06T12:00:00+00:00/PT1H", "value": 1.0 },
+                { "validTime": "2024-09-06T13:00:00+00:00/PT1H", "value": null },
+                { "validTime": "2024-09-06T15:00:00+00:00/PT1H", "value": 3.0 },
+            ]),
+        )
+        .try_expand(starting_at(), Kind::Length)
+        .unwrap();
+
+        assert_eq!(hours, [None, Some(1.0), Some(1.0), Some(1.0), Some(3.0)]);
+    }
+
+    #[test]
+    fn layers_without_values_are_all_none() {
+        let hours = layer(
+            "wmoUnit:percent",
+            serde_json::json!([
+                { "validTime": "2024-09-06T11:00:00+00:00/PT2H", "value": null },
+            ]),
+        )
+        .try_expand(starting_at(), Kind::Percent)
+        .unwrap();
+
+        assert_eq!(hours, [None, None]);
+
+        let hours = layer("wmoUnit:percent", serde_json::json!([]))
+            .try_expand(starting_at(), Kind::Percent)
+            .unwrap();
+
+        assert!(hours.is_empty());
+    }
+
+    #[test]
+    fn errors_name_the_layer() {
+        let mut gridpoint = serde_json::json!({
+            "properties": {
+                "updateTime": "2024-09-06T11:54:57+00:00",
+                "validTimes": "2024-09-06T11:00:00+00:00/P7DT5H",
+            }
+        });
+        for (name, uom) in [
+            ("waveHeight", "wmoUnit:m"),
+            ("wavePeriod", "nwsUnit:s"),
+            ("waveDirection", "wmoUnit:degree_(angle)"),
+            ("windSpeed", "wmoUnit:km_h-1"),
+            ("windGust", "wmoUnit:km_h-1"),
+            ("windDirection", "wmoUnit:degree_(angle)"),
+            ("temperature", "wmoUnit:degC"),
+            ("dewpoint", "wmoUnit:degC"),
+            ("probabilityOfPrecipitation", "wmoUnit:percent"),
+            ("probabilityOfThunder", "wmoUnit:percent"),
+            ("skyCover", "wmoUnit:percent"),
+        ] {
+            gridpoint["properties"][name] = serde_json::json!({
+                "uom": uom,
+                "values": [{ "validTime": "2024-09-06T11:00:00+00:00/PT1H", "value": 1.0 }]
+            });
+        }
+        assert!(Nws::try_parse(&gridpoint.to_string()).is_ok());
+
+        // Only the layers the quality is rated from are required
+        gridpoint["properties"]["probabilityOfThunder"]["values"] = serde_json::json!([]);
+        gridpoint["properties"]["skyCover"]["values"][0]["value"] = serde_json::Value::Null;
+        let forecast = Nws::try_parse(&gridpoint.to_string()).unwrap();
+
+        assert_eq!(forecast.probability_of_thunder, [None]);
+        assert_eq!(forecast.cloud_cover, [None]);
+
+        // The forecast starts once every layer the quality needs has a value
+        gridpoint["properties"]["waveHeight"]["values"] = serde_json::json!([
+            { "validTime": "2024-09-06T11:00:00+00:00/PT1H", "value": null },
+            { "validTime": "2024-09-06T12:00:00+00:00/PT2H", "value": 1.0 },
+        ]);
+        for name in ["windSpeed", "windDirection", "temperature"] {
+            gridpoint["properties"][name]["values"] = serde_json::json!([
+                { "validTime": "2024-09-06T11:00:00+00:00/PT3H", "value": 1.0 },
+            ]);
+        }
+        let forecast = Nws::try_parse(&gridpoint.to_string()).unwrap();
+
+        assert_eq!(forecast.starting_at, starting_at() + TimeDelta::hours(1));
+        assert_eq!(forecast.wave_height, [1.0, 1.0]);
+        assert_eq!(forecast.temperature, [Some(1.0), Some(1.0)]);
+        assert_eq!(forecast.cloud_cover, [None, None]);
+
+        gridpoint["properties"]["windSpeed"]["values"] = serde_json::json!([]);
+        let error = Nws::try_parse(&gridpoint.to_string()).unwrap_err();
+
+        assert_eq!(error.to_string(), "Invalid windSpeed layer: no values");
+
+        gridpoint["properties"]["windSpeed"]["values"] =
+            gridpoint["properties"]["windGust"]["values"].clone();
+
+        gridpoint["properties"]["temperature"]["uom"] = serde_json::json!("wmoUnit:K");
+        let error = Nws::try_parse(&gridpoint.to_string()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid temperature layer: Unknown unit of measure wmoUnit:K"
+        );
+
+        gridpoint["properties"]["temperature"]["uom"] = serde_json::json!("wmoUnit:km_h-1");
+        let error = Nws::try_parse(&gridpoint.to_string()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid temperature layer: KilometerPerHour isn't a unit of Temperature"
+        );
+
+        gridpoint["properties"]["temperature"]["uom"] = serde_json::json!("wmoUnit:degC");
+        gridpoint["properties"]["windGust"]["values"][0]["validTime"] =
+            serde_json::json!("2024-09-06T11:00:00+00:00/1H");
+
+        let error = Nws::try_parse(&gridpoint.to_string()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid windGust layer: 1H isn't a duration"
+        );
+
+        gridpoint["properties"]
+            .as_object_mut()
+            .unwrap()
+            .remove("skyCover");
+        let error = Nws::try_parse(&gridpoint.to_string()).unwrap_err();
+
+        assert!(error.to_string().contains("missing field `skyCover`"));
+    }
+}
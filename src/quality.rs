@@ -1,3 +1,5 @@
+use crate::angular_difference;
+
 /// Contains quality text and associated color.
 #[derive(Debug)]
 pub struct Quality(pub &'static str, pub &'static str);
@@ -8,102 +10,377 @@ pub const POOR: Quality = Quality("Poor", "#ff9500");
 pub const VERY_POOR: Quality = Quality("Very Poor", "#f4496d");
 pub const FLAT: Quality = Quality("Flat", "#a8a29e");
 
-const HIGH_WIND: f64 = 25.0;
+/// Wind speed, in mph, under which it's glassy whatever the direction.
+const GLASSY_WIND: f64 = 5.0;
 
-impl Quality {
-    fn basic_wave_check(wave_height: f64) -> Option<&'static Self> {
-        if wave_height < 0.98 {
-            return Some(&FLAT);
-        }
+/// Periods, in seconds, under which waves are short, choppy windswell and at or
+/// over which they're clean groundswell.
+const WINDSWELL_PERIOD: f64 = 5.0;
+const GROUNDSWELL_PERIOD: f64 = 8.0;
+
+/// Qualities a break can be when it isn't flat, worst to best.
+const RANKED: [&Quality; 4] = [&VERY_POOR, &POOR, &OK, &GOOD];
+
+/// The conditions at a spot at a point in time. Heights are in feet, speeds
+/// in mph, periods in seconds and directions in degrees the wind or swell is
+/// coming from. `None` when no station or model reported it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Conditions {
+    pub wave_height: Option<f64>,
+    pub wave_period: Option<f64>,
+    pub wave_direction: Option<f64>,
+    pub wind_speed: Option<f64>,
+    pub wind_direction: Option<f64>,
+}
+
+/// Compass directions, clockwise from `from` up to but not including `to`.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct DirectionWindow {
+    pub from: f64,
+    pub to: f64,
+}
 
-        None
+impl DirectionWindow {
+    /// Window of everything in front of a beach facing the bearing.
+    pub fn facing(bearing: f64) -> Self {
+        Self {
+            from: (bearing - 90.0).rem_euclid(360.0),
+            to: (bearing + 90.0).rem_euclid(360.0),
+        }
     }
 
-    pub fn north(wave_height: f64, wind_speed: f64, wind_direction: f64) -> &'static Self {
-        if let Some(quality) = Self::basic_wave_check(wave_height) {
-            return quality;
+    /// Whether the window contains the direction, 0° and 360° both being north.
+    pub fn contains(&self, direction: f64) -> bool {
+        let [direction, from, to] = [direction, self.from, self.to].map(|v| v.rem_euclid(360.0));
+
+        if from <= to {
+            (from..to).contains(&direction)
+        } else {
+            // Wraps past north
+            direction >= from || direction < to
         }
+    }
+}
+
+/// How a spot reacts to the wind, as declared per spot in `config/spots.yml`.
+#[derive(serde::Deserialize, Debug)]
+pub struct QualityProfile {
+    /// Wind directions blowing straight from the land, good however strong
+    #[serde(default)]
+    pub straight_offshore: Vec<DirectionWindow>,
+    /// Wind directions blowing from the land
+    pub offshore: Vec<DirectionWindow>,
+    /// Wind directions blowing along the beach
+    pub cross_shore: Vec<DirectionWindow>,
+    /// Wind directions blowing in from the lake
+    pub onshore: Vec<DirectionWindow>,
+    /// Wave height, in feet, under which the spot is flat
+    pub min_height: f64,
+    /// Wind speed, in mph, over which each window is a step worse
+    pub high_wind: f64,
+}
+
+/// The rule of a spot's quality profile deciding its quality.
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityRule {
+    Flat,
+    Glassy,
+    Offshore,
+    CrossShore,
+    Onshore,
+    /// Wind from outside every window of the profile
+    OtherWind,
+    /// No reading of the wind, rated from the waves alone
+    UnknownWind,
+}
 
-        if wind_speed < 5.0 {
-            return &GOOD;
+/// An adjustment made to the wind based quality for the swell.
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SwellRule {
+    OutsideWindow,
+    Windswell,
+    Groundswell,
+}
+
+/// Why a spot got its quality, e.g. "onshore wind 18 mph from 95°".
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+pub struct QualityReason {
+    pub rule: QualityRule,
+    /// Whether the wind was over the spot's high wind threshold
+    pub high_wind: bool,
+    pub swell: Option<SwellRule>,
+    pub text: String,
+}
+
+/// The risk of rip currents, as the NWS words it in its surf zone forecast.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RipCurrentRisk {
+    Low,
+    Moderate,
+    High,
+}
+
+impl RipCurrentRisk {
+    /// The color of the quality the risk is as bad as.
+    pub fn color(&self) -> &'static str {
+        match self {
+            RipCurrentRisk::Low => GOOD.1,
+            RipCurrentRisk::Moderate => OK.1,
+            RipCurrentRisk::High => VERY_POOR.1,
         }
+    }
+}
 
-        // Essentially offshore
-        if (300.0..340.0).contains(&wind_direction) {
-            return &GOOD;
+/// Wave heights, in feet, over which rip currents are a moderate or high risk
+/// with onshore wind, and a moderate risk whatever the wind.
+const MODERATE_RIP_WAVES: f64 = 2.0;
+const HIGH_RIP_WAVES: f64 = 4.0;
+const ANY_WIND_RIP_WAVES: f64 = 3.0;
+/// Wind speed, in mph, under which onshore wind doesn't push water at the beach.
+const RIP_WIND: f64 = 10.0;
+
+/// A quality along with the reason for it.
+#[derive(Debug, Clone)]
+pub struct Rating {
+    pub quality: &'static Quality,
+    pub reason: QualityReason,
+}
+
+impl Rating {
+    fn new(quality: &'static Quality, rule: QualityRule, high_wind: bool, text: String) -> Self {
+        Self {
+            quality,
+            reason: QualityReason {
+                rule,
+                high_wind,
+                swell: None,
+                text,
+            },
         }
+    }
 
-        // Primarily north wind
-        if (0.0..70.0).contains(&wind_direction) || (270.0..361.0).contains(&wind_direction) {
-            if wind_speed <= HIGH_WIND {
-                return &GOOD;
-            }
-            return &OK;
+    /// Adjusts a wind based rating for the swell. Swell from outside the spot's
+    /// window doesn't reach it cleanly so it's at best poor, short period
+    /// windswell is a step worse and long period groundswell a step better.
+    pub fn with_swell(
+        self,
+        wave_period: Option<f64>,
+        wave_direction: Option<f64>,
+        swell_window: &DirectionWindow,
+    ) -> Self {
+        let Some(rank) = RANKED.iter().position(|q| q.0 == self.quality.0) else {
+            // Flat stays flat
+            return self;
+        };
+
+        let (quality, swell, text) = match (wave_direction, wave_period) {
+            (Some(direction), _) if !swell_window.contains(direction) => (
+                RANKED[rank.min(1)],
+                SwellRule::OutsideWindow,
+                format!("swell from {direction:.0}° outside the window"),
+            ),
+            (_, Some(period)) if period < WINDSWELL_PERIOD => (
+                RANKED[rank.saturating_sub(1)],
+                SwellRule::Windswell,
+                format!("short period windswell ({period:.0} s)"),
+            ),
+            (_, Some(period)) if period >= GROUNDSWELL_PERIOD => (
+                RANKED[(rank + 1).min(RANKED.len() - 1)],
+                SwellRule::Groundswell,
+                format!("long period groundswell ({period:.0} s)"),
+            ),
+            _ => return self,
+        };
+
+        Self {
+            quality,
+            reason: QualityReason {
+                swell: Some(swell),
+                text: format!("{}, {text}", self.reason.text),
+                ..self.reason
+            },
         }
+    }
+}
 
-        if (70.0..120.0).contains(&wind_direction) || (230.0..270.0).contains(&wind_direction) {
-            if wind_speed <= HIGH_WIND {
-                return &OK;
-            }
-            return &POOR;
+impl QualityProfile {
+    /// Rates the wave height and wind. Without a wave height it isn't called
+    /// flat, and without the wind the waves are rated fair to good.
+    pub fn get_quality(
+        &self,
+        wave_height: Option<f64>,
+        wind_speed: Option<f64>,
+        wind_direction: Option<f64>,
+    ) -> Rating {
+        if wave_height.is_some_and(|height| height < self.min_height) {
+            return Rating::new(
+                &FLAT,
+                QualityRule::Flat,
+                false,
+                format!("flat: waves under {} ft", round_to_tenth(self.min_height)),
+            );
         }
 
-        if (120.0..230.0).contains(&wind_direction) {
-            if wind_speed <= HIGH_WIND {
-                return &POOR;
-            }
-            return &VERY_POOR;
+        if wind_speed.is_some_and(|speed| speed < GLASSY_WIND) {
+            return Rating::new(
+                &GOOD,
+                QualityRule::Glassy,
+                false,
+                format!("glassy: wind under {GLASSY_WIND} mph"),
+            );
         }
 
-        &POOR
+        let (Some(wind_speed), Some(wind_direction)) = (wind_speed, wind_direction) else {
+            return Rating::new(
+                &OK,
+                QualityRule::UnknownWind,
+                false,
+                "wind unknown".to_string(),
+            );
+        };
+        // North is reported as both 0° and 360°
+        let wind_direction = wind_direction.rem_euclid(360.0);
+
+        let in_window =
+            |windows: &[DirectionWindow]| windows.iter().any(|w| w.contains(wind_direction));
+        let high_wind = wind_speed > self.high_wind;
+
+        let (rule, light, strong) = if in_window(&self.straight_offshore) {
+            (QualityRule::Offshore, &GOOD, &GOOD)
+        } else if in_window(&self.offshore) {
+            (QualityRule::Offshore, &GOOD, &OK)
+        } else if in_window(&self.cross_shore) {
+            (QualityRule::CrossShore, &OK, &POOR)
+        } else if in_window(&self.onshore) {
+            (QualityRule::Onshore, &POOR, &VERY_POOR)
+        } else {
+            (QualityRule::OtherWind, &POOR, &POOR)
+        };
+
+        let label = match rule {
+            QualityRule::Offshore => "offshore ",
+            QualityRule::CrossShore => "cross-shore ",
+            QualityRule::Onshore => "onshore ",
+            _ => "",
+        };
+        let strength = if high_wind { "strong " } else { "" };
+
+        Rating::new(
+            if high_wind { strong } else { light },
+            rule,
+            high_wind,
+            format!("{strength}{label}wind {wind_speed:.0} mph from {wind_direction:.0}°"),
+        )
     }
 
-    pub fn south(wave_height: f64, wind_speed: f64, wind_direction: f64) -> &'static Self {
-        if let Some(quality) = Self::basic_wave_check(wave_height) {
-            return quality;
-        }
+    /// Estimates the rip current risk for when the NWS hasn't issued a surf zone
+    /// forecast. Onshore wind piles water against the beach which rushes back
+    /// out through rips, more so the bigger the waves. Onshore wind without a
+    /// wave height is a moderate risk, unknown wind is only judged by the waves.
+    pub fn estimate_rip_current_risk(
+        &self,
+        wave_height: Option<f64>,
+        wind_speed: Option<f64>,
+        wind_direction: Option<f64>,
+    ) -> RipCurrentRisk {
+        let onshore = wind_speed.is_some_and(|speed| speed >= RIP_WIND)
+            && wind_direction
+                .is_some_and(|direction| self.onshore.iter().any(|w| w.contains(direction)));
 
-        if wind_speed < 5.0 {
-            return &GOOD;
+        match (onshore, wave_height) {
+            (true, Some(height)) if height >= HIGH_RIP_WAVES => RipCurrentRisk::High,
+            (true, Some(height)) if height >= MODERATE_RIP_WAVES => RipCurrentRisk::Moderate,
+            (true, None) => RipCurrentRisk::Moderate,
+            (_, Some(height)) if height >= ANY_WIND_RIP_WAVES => RipCurrentRisk::Moderate,
+            _ => RipCurrentRisk::Low,
         }
+    }
+}
 
-        // Essentially offshore
-        if (240.0..310.0).contains(&wind_direction) {
-            return &GOOD;
-        }
+/// Weights of the wind and period in how clean the waves are, summing to one.
+const WIND_WEIGHT: f64 = 0.6;
+const PERIOD_WEIGHT: f64 = 0.4;
 
-        if (120.0..330.0).contains(&wind_direction) {
-            if wind_speed <= HIGH_WIND {
-                return &GOOD;
-            }
-            return &OK;
-        }
+/// A 0 to 10 rating of the conditions, so hours and spots can be ranked,
+/// along with the categorical quality.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+pub struct Score {
+    pub value: f64,
+    pub breakdown: ScoreBreakdown,
+    pub label: &'static str,
+    pub color: &'static str,
+}
 
-        if wind_direction >= 330.0 {
-            if wind_speed <= HIGH_WIND {
-                return &OK;
-            }
-            return &POOR;
-        }
+/// Each factor of a score rated 0 to 10, higher is better.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+pub struct ScoreBreakdown {
+    pub size: f64,
+    pub wind_speed: f64,
+    pub wind_angle: f64,
+    pub period: f64,
+}
 
-        if (80.0..120.0).contains(&wind_direction) {
-            if wind_speed <= HIGH_WIND {
-                return &OK;
-            }
-            return &POOR;
-        }
+impl Score {
+    /// Scores the conditions at a beach facing `bearing`. A missing wave height,
+    /// period or wind is rated as average rather than guessed at.
+    pub fn new(conditions: &Conditions, bearing: f64, quality: &'static Quality) -> Self {
+        let Conditions {
+            wave_height,
+            wave_period,
+            wind_speed,
+            wind_direction,
+            ..
+        } = *conditions;
 
-        if (0.0..80.0).contains(&wind_direction) {
-            if wind_speed <= HIGH_WIND {
-                return &POOR;
-            }
-            return &VERY_POOR;
-        }
+        // Nothing counts when it's flat, from there the bigger the better up to
+        // five feet, with each extra foot counting a little less.
+        let size = match wave_height {
+            _ if quality.0 == FLAT.0 => 0.0,
+            Some(height) => (height / 5.0).min(1.0).sqrt(),
+            None => 0.5,
+        };
 
-        &POOR
+        // Anything under 5 mph is glassy, 30 mph and over is blown out.
+        let wind_speed_factor =
+            wind_speed.map_or(0.5, |speed| 1.0 - ((speed - 5.0) / 25.0).clamp(0.0, 1.0));
+
+        // Offshore wind blows from the land, opposite the way the beach faces.
+        let offshore = (bearing + 180.0).rem_euclid(360.0);
+        let wind_angle = wind_direction.map_or(0.5, |direction| {
+            (1.0 + angular_difference(direction, offshore).to_radians().cos()) / 2.0
+        });
+
+        // A strong wind is only as good as its angle, a light one barely matters.
+        let wind = wind_speed_factor + (1.0 - wind_speed_factor) * wind_angle;
+
+        // 3 second windswell up to a 10 second groundswell.
+        let period = wave_period.map_or(0.5, |period| ((period - 3.0) / 7.0).clamp(0.0, 1.0));
+
+        // Clean conditions don't make up for a lack of waves, so size scales
+        // everything else.
+        let value = 10.0 * size * (WIND_WEIGHT * wind + PERIOD_WEIGHT * period);
+
+        Self {
+            value: round_to_tenth(value),
+            breakdown: ScoreBreakdown {
+                size: round_to_tenth(10.0 * size),
+                wind_speed: round_to_tenth(10.0 * wind_speed_factor),
+                wind_angle: round_to_tenth(10.0 * wind_angle),
+                period: round_to_tenth(10.0 * period),
+            },
+            label: quality.0,
+            color: quality.1,
+        }
     }
 }
 
+pub(crate) fn round_to_tenth(v: f64) -> f64 {
+    (v * 10.0).round() / 10.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,27 +396,124 @@ mod tests {
     const SOUTH_WEST_WIND: f64 = 275.0;
     const NORTH_WEST_WIND: f64 = 95.0;
 
+    fn rated(quality: &'static Quality) -> Rating {
+        Rating::new(quality, QualityRule::OtherWind, false, String::new())
+    }
+
+    fn window(from: f64, to: f64) -> DirectionWindow {
+        DirectionWindow { from, to }
+    }
+
+    /// A beach along the north shore, facing south.
+    fn north_profile() -> QualityProfile {
+        QualityProfile {
+            straight_offshore: vec![window(300.0, 340.0)],
+            offshore: vec![window(270.0, 70.0)],
+            cross_shore: vec![window(70.0, 120.0), window(230.0, 270.0)],
+            onshore: vec![window(120.0, 230.0)],
+            min_height: 0.98,
+            high_wind: 25.0,
+        }
+    }
+
+    /// Atwater, Bradford and Sheboygan-North's profile in config/spots.yml.
+    fn south_profile() -> QualityProfile {
+        QualityProfile {
+            straight_offshore: vec![window(240.0, 310.0)],
+            offshore: vec![window(120.0, 330.0)],
+            cross_shore: vec![window(330.0, 0.0), window(80.0, 120.0)],
+            onshore: vec![window(0.0, 80.0)],
+            min_height: 0.98,
+            high_wind: 25.0,
+        }
+    }
+
     #[test]
-    fn basic_wave_check_works_when_its_not_flat() {
-        assert!(Quality::basic_wave_check(2.1).is_none());
+    fn north_wind_is_the_same_at_0_and_360_degrees() {
+        for profile in [north_profile(), south_profile()] {
+            let quality = |wind_direction| {
+                profile
+                    .get_quality(Some(HIGH_WAVES), Some(LOW_WIND), Some(wind_direction))
+                    .quality
+                    .0
+            };
+
+            assert_eq!(quality(0.0), quality(360.0));
+        }
+
+        let profile = south_profile();
+        let rating = |wind_direction| {
+            profile.get_quality(Some(HIGH_WAVES), Some(LOW_WIND), Some(wind_direction))
+        };
+
+        assert_eq!(rating(0.0).quality.0, "Poor");
+        assert_eq!(rating(360.0).quality.0, "Poor");
+        assert_eq!(rating(360.0).reason.text, "onshore wind 6 mph from 0°");
+        assert_eq!(rating(359.0).quality.0, "Fair to Good");
+        assert_eq!(rating(330.0).quality.0, "Fair to Good");
     }
 
     #[test]
-    fn basic_wave_check_works_when_it_is_flat() {
-        assert!(Quality::basic_wave_check(0.1).is_some());
-        assert_eq!(Quality::basic_wave_check(0.1).unwrap().0, "Flat");
-        assert_eq!(Quality::basic_wave_check(0.1).unwrap().1, "#a8a29e");
+    fn windows_ending_at_0_and_360_degrees_are_the_same() {
+        for window in [window(330.0, 0.0), window(330.0, 360.0)] {
+            assert!(window.contains(359.0));
+            assert!(!window.contains(0.0));
+            assert!(!window.contains(360.0));
+        }
+        assert!(window(0.0, 80.0).contains(360.0));
+        assert!(window(360.0, 80.0).contains(0.0));
+    }
+
+    #[test]
+    fn a_beach_is_flat_under_its_minimum_height() {
+        let profile = north_profile();
+
+        assert_eq!(
+            profile
+                .get_quality(Some(0.5), Some(LOW_WIND), Some(SOUTH_WIND))
+                .quality
+                .0,
+            "Flat"
+        );
+        assert_eq!(
+            profile
+                .get_quality(Some(2.1), Some(LOW_WIND), Some(SOUTH_WIND))
+                .quality
+                .0,
+            "Good"
+        );
+
+        let bigger_wave_spot = QualityProfile {
+            min_height: 2.5,
+            ..north_profile()
+        };
+        assert_eq!(
+            bigger_wave_spot
+                .get_quality(Some(2.1), Some(LOW_WIND), Some(SOUTH_WIND))
+                .quality
+                .1,
+            "#a8a29e"
+        );
     }
 
     #[test]
     fn a_north_beach_should_be_good_in_some_condition() {
-        assert_eq!(Quality::north(HIGH_WAVES, LOW_WIND, SOUTH_WIND).0, "Good");
+        assert_eq!(
+            north_profile()
+                .get_quality(Some(HIGH_WAVES), Some(LOW_WIND), Some(SOUTH_WIND))
+                .quality
+                .0,
+            "Good"
+        );
     }
 
     #[test]
     fn a_north_beach_shoud_be_bad_in_some_condition() {
         assert_eq!(
-            Quality::north(HIGH_WAVES, HIGH_WIND, NORTH_WIND).0,
+            north_profile()
+                .get_quality(Some(HIGH_WAVES), Some(HIGH_WIND), Some(NORTH_WIND))
+                .quality
+                .0,
             "Very Poor"
         );
     }
@@ -147,7 +521,10 @@ mod tests {
     #[test]
     fn a_north_beach_shoud_be_ok_in_some_condition() {
         assert_eq!(
-            Quality::north(HIGH_WAVES, HIGH_WIND, SOUTH_WEST_WIND).0,
+            north_profile()
+                .get_quality(Some(HIGH_WAVES), Some(HIGH_WIND), Some(SOUTH_WEST_WIND))
+                .quality
+                .0,
             "Fair to Good"
         );
     }
@@ -155,13 +532,405 @@ mod tests {
     #[test]
     fn a_north_beach_shoud_be_poor_in_some_condition() {
         assert_eq!(
-            Quality::north(HIGH_WAVES, HIGH_WIND, NORTH_WEST_WIND).0,
+            north_profile()
+                .get_quality(Some(HIGH_WAVES), Some(HIGH_WIND), Some(NORTH_WEST_WIND))
+                .quality
+                .0,
             "Poor"
         );
     }
 
     #[test]
     fn a_north_beach_shoud_be_flat_in_some_condition() {
-        assert_eq!(Quality::north(SMALL_WAVES, HIGH_WIND, NORTH_WIND).0, "Flat");
+        assert_eq!(
+            north_profile()
+                .get_quality(Some(SMALL_WAVES), Some(HIGH_WIND), Some(NORTH_WIND))
+                .quality
+                .0,
+            "Flat"
+        );
+    }
+
+    #[test]
+    fn the_high_wind_threshold_is_per_spot() {
+        let sheltered = QualityProfile {
+            high_wind: 35.0,
+            ..north_profile()
+        };
+
+        assert_eq!(
+            sheltered
+                .get_quality(Some(HIGH_WAVES), Some(HIGH_WIND), Some(NORTH_WIND))
+                .quality
+                .0,
+            "Poor"
+        );
+    }
+
+    #[test]
+    fn wind_outside_every_window_is_poor() {
+        let profile = QualityProfile {
+            cross_shore: vec![],
+            ..north_profile()
+        };
+
+        assert_eq!(
+            profile
+                .get_quality(Some(HIGH_WAVES), Some(LOW_WIND), Some(NORTH_WEST_WIND))
+                .quality
+                .0,
+            "Poor"
+        );
+    }
+
+    #[test]
+    fn straight_offshore_wind_is_good_however_strong() {
+        let profile = north_profile();
+        let quality = |wind_direction| {
+            profile
+                .get_quality(Some(HIGH_WAVES), Some(HIGH_WIND), Some(wind_direction))
+                .quality
+                .0
+        };
+
+        assert_eq!(quality(300.0), "Good");
+        assert_eq!(quality(320.0), "Good");
+        assert_eq!(quality(340.0), "Fair to Good");
+        assert_eq!(quality(290.0), "Fair to Good");
+    }
+
+    #[test]
+    fn windows_include_their_start_but_not_their_end() {
+        let profile = north_profile();
+        let quality = |wind_direction| {
+            profile
+                .get_quality(Some(HIGH_WAVES), Some(HIGH_WIND), Some(wind_direction))
+                .quality
+                .0
+        };
+
+        assert_eq!(quality(70.0), "Poor");
+        assert_eq!(quality(120.0), "Very Poor");
+        assert_eq!(quality(230.0), "Poor");
+        assert_eq!(quality(270.0), "Fair to Good");
+        assert_eq!(quality(360.0), "Fair to Good");
+    }
+
+    const EAST_FACING: f64 = 90.0;
+    const OFFSHORE_WIND: f64 = 270.0;
+    const ONSHORE_WIND: f64 = 90.0;
+
+    const EAST_SWELL: f64 = 90.0;
+    const WEST_SWELL: f64 = 270.0;
+
+    fn conditions(
+        wave_height: f64,
+        wave_period: f64,
+        wind_speed: f64,
+        wind_direction: f64,
+    ) -> Conditions {
+        Conditions {
+            wave_height: Some(wave_height),
+            wave_period: Some(wave_period),
+            wave_direction: None,
+            wind_speed: Some(wind_speed),
+            wind_direction: Some(wind_direction),
+        }
+    }
+
+    #[test]
+    fn a_flat_score_is_zero() {
+        let score = Score::new(&conditions(SMALL_WAVES, 8.0, 0.0, 0.0), EAST_FACING, &FLAT);
+
+        assert_eq!(score.value, 0.0);
+        assert_eq!(score.label, "Flat");
+    }
+
+    #[test]
+    fn bigger_waves_score_higher_with_the_same_label() {
+        let small = Score::new(
+            &conditions(1.0, 5.0, LOW_WIND, OFFSHORE_WIND),
+            EAST_FACING,
+            &GOOD,
+        );
+        let big = Score::new(
+            &conditions(5.0, 5.0, LOW_WIND, OFFSHORE_WIND),
+            EAST_FACING,
+            &GOOD,
+        );
+
+        assert_eq!(small.label, big.label);
+        assert!(big.value > small.value);
+    }
+
+    #[test]
+    fn offshore_wind_scores_higher_than_onshore() {
+        let offshore = Score::new(
+            &conditions(3.0, 5.0, HIGH_WIND, OFFSHORE_WIND),
+            EAST_FACING,
+            &OK,
+        );
+        let onshore = Score::new(
+            &conditions(3.0, 5.0, HIGH_WIND, ONSHORE_WIND),
+            EAST_FACING,
+            &OK,
+        );
+
+        assert_eq!(offshore.breakdown.wind_angle, 10.0);
+        assert_eq!(onshore.breakdown.wind_angle, 0.0);
+        assert!(offshore.value > onshore.value);
+    }
+
+    #[test]
+    fn a_longer_period_scores_higher() {
+        let windswell = Score::new(
+            &conditions(3.0, 4.0, LOW_WIND, ONSHORE_WIND),
+            EAST_FACING,
+            &OK,
+        );
+        let groundswell = Score::new(
+            &conditions(3.0, 9.0, LOW_WIND, ONSHORE_WIND),
+            EAST_FACING,
+            &OK,
+        );
+
+        assert!(groundswell.breakdown.period > windswell.breakdown.period);
+        assert!(groundswell.value > windswell.value);
+    }
+
+    #[test]
+    fn perfect_conditions_score_ten() {
+        let score = Score::new(
+            &conditions(HIGH_WAVES, 10.0, 0.0, OFFSHORE_WIND),
+            EAST_FACING,
+            &GOOD,
+        );
+
+        assert_eq!(score.value, 10.0);
+    }
+
+    #[test]
+    fn swell_window_contains_directions_in_front_of_the_beach() {
+        let window = DirectionWindow::facing(EAST_FACING);
+
+        assert!(window.contains(EAST_SWELL));
+        assert!(window.contains(0.0));
+        assert!(!window.contains(WEST_SWELL));
+    }
+
+    #[test]
+    fn swell_window_wraps_past_north() {
+        let window = DirectionWindow {
+            from: 300.0,
+            to: 60.0,
+        };
+
+        assert!(window.contains(350.0));
+        assert!(window.contains(10.0));
+        assert!(window.contains(370.0));
+        assert!(!window.contains(180.0));
+    }
+
+    #[test]
+    fn short_period_windswell_is_a_step_worse() {
+        let window = DirectionWindow::facing(EAST_FACING);
+
+        assert_eq!(
+            rated(&GOOD)
+                .with_swell(Some(4.0), Some(EAST_SWELL), &window)
+                .quality
+                .0,
+            "Fair to Good"
+        );
+        assert_eq!(
+            rated(&POOR).with_swell(Some(3.0), None, &window).quality.0,
+            "Very Poor"
+        );
+        assert_eq!(
+            rated(&VERY_POOR)
+                .with_swell(Some(3.0), None, &window)
+                .quality
+                .0,
+            "Very Poor"
+        );
+    }
+
+    #[test]
+    fn long_period_groundswell_is_a_step_better() {
+        let window = DirectionWindow::facing(EAST_FACING);
+
+        assert_eq!(
+            rated(&POOR)
+                .with_swell(Some(8.0), Some(EAST_SWELL), &window)
+                .quality
+                .0,
+            "Fair to Good"
+        );
+        assert_eq!(
+            rated(&GOOD).with_swell(Some(10.0), None, &window).quality.0,
+            "Good"
+        );
+    }
+
+    #[test]
+    fn mid_period_swell_leaves_the_quality_alone() {
+        let window = DirectionWindow::facing(EAST_FACING);
+
+        assert_eq!(
+            rated(&OK)
+                .with_swell(Some(6.0), Some(EAST_SWELL), &window)
+                .quality
+                .0,
+            "Fair to Good"
+        );
+        assert_eq!(
+            rated(&OK).with_swell(None, None, &window).quality.0,
+            "Fair to Good"
+        );
+    }
+
+    #[test]
+    fn swell_outside_the_window_is_at_best_poor() {
+        let window = DirectionWindow::facing(EAST_FACING);
+
+        assert_eq!(
+            rated(&GOOD)
+                .with_swell(Some(9.0), Some(WEST_SWELL), &window)
+                .quality
+                .0,
+            "Poor"
+        );
+        assert_eq!(
+            rated(&VERY_POOR)
+                .with_swell(Some(9.0), Some(WEST_SWELL), &window)
+                .quality
+                .0,
+            "Very Poor"
+        );
+    }
+
+    #[test]
+    fn flat_stays_flat_whatever_the_swell() {
+        let window = DirectionWindow::facing(EAST_FACING);
+
+        assert_eq!(
+            rated(&FLAT)
+                .with_swell(Some(12.0), Some(WEST_SWELL), &window)
+                .quality
+                .0,
+            "Flat"
+        );
+    }
+
+    #[test]
+    fn the_reason_names_the_rule_that_fired() {
+        let profile = north_profile();
+
+        let onshore = profile
+            .get_quality(Some(HIGH_WAVES), Some(18.0), Some(160.0))
+            .reason;
+        assert_eq!(onshore.rule, QualityRule::Onshore);
+        assert!(!onshore.high_wind);
+        assert_eq!(onshore.text, "onshore wind 18 mph from 160°");
+
+        let flat = profile
+            .get_quality(Some(SMALL_WAVES), Some(LOW_WIND), Some(SOUTH_WIND))
+            .reason;
+        assert_eq!(flat.rule, QualityRule::Flat);
+        assert_eq!(flat.text, "flat: waves under 1 ft");
+
+        let glassy = profile
+            .get_quality(Some(HIGH_WAVES), Some(2.0), Some(NORTH_WIND))
+            .reason;
+        assert_eq!(glassy.rule, QualityRule::Glassy);
+    }
+
+    #[test]
+    fn unknown_wind_is_rated_from_the_waves_alone() {
+        let profile = north_profile();
+        let window = DirectionWindow::facing(EAST_FACING);
+
+        let rating = profile.get_quality(Some(HIGH_WAVES), None, None);
+        assert_eq!(rating.quality.0, "Fair to Good");
+        assert_eq!(rating.reason.rule, QualityRule::UnknownWind);
+        assert_eq!(rating.reason.text, "wind unknown");
+
+        let rating = profile
+            .get_quality(Some(HIGH_WAVES), Some(HIGH_WIND), None)
+            .with_swell(Some(12.0), Some(EAST_SWELL), &window);
+        assert_eq!(rating.quality.0, "Good");
+
+        // Light wind is glassy whichever way it blows
+        let glassy = profile.get_quality(Some(HIGH_WAVES), Some(2.0), None);
+        assert_eq!(glassy.reason.rule, QualityRule::Glassy);
+
+        let score = Score::new(&Conditions::default(), EAST_FACING, rating.quality);
+        assert_eq!(score.breakdown.wind_speed, 5.0);
+        assert_eq!(score.breakdown.wind_angle, 5.0);
+    }
+
+    #[test]
+    fn an_unknown_wave_height_isnt_flat() {
+        let rating = north_profile().get_quality(None, Some(HIGH_WIND), Some(NORTH_WIND));
+
+        assert_eq!(rating.quality.0, "Very Poor");
+        assert_eq!(rating.reason.rule, QualityRule::Onshore);
+    }
+
+    #[test]
+    fn the_reason_notes_high_wind_and_the_swell() {
+        let window = DirectionWindow::facing(EAST_FACING);
+        let rating = north_profile()
+            .get_quality(Some(HIGH_WAVES), Some(HIGH_WIND), Some(SOUTH_WIND))
+            .with_swell(Some(4.0), Some(EAST_SWELL), &window);
+
+        assert_eq!(rating.quality.0, "Poor");
+        assert_eq!(rating.reason.rule, QualityRule::Offshore);
+        assert!(rating.reason.high_wind);
+        assert_eq!(rating.reason.swell, Some(SwellRule::Windswell));
+        assert_eq!(
+            rating.reason.text,
+            "strong offshore wind 33 mph from 0°, short period windswell (4 s)"
+        );
+    }
+
+    #[test]
+    fn onshore_wind_and_big_waves_raise_the_rip_current_risk() {
+        let profile = north_profile();
+
+        assert_eq!(
+            profile.estimate_rip_current_risk(Some(HIGH_WAVES), Some(HIGH_WIND), Some(NORTH_WIND)),
+            RipCurrentRisk::High
+        );
+        assert_eq!(
+            profile.estimate_rip_current_risk(Some(2.5), Some(HIGH_WIND), Some(NORTH_WIND)),
+            RipCurrentRisk::Moderate
+        );
+        // Light onshore wind doesn't count
+        assert_eq!(
+            profile.estimate_rip_current_risk(Some(2.5), Some(LOW_WIND), Some(NORTH_WIND)),
+            RipCurrentRisk::Low
+        );
+        assert_eq!(
+            profile.estimate_rip_current_risk(Some(HIGH_WAVES), Some(HIGH_WIND), Some(SOUTH_WIND)),
+            RipCurrentRisk::Moderate
+        );
+        assert_eq!(
+            profile.estimate_rip_current_risk(Some(SMALL_WAVES), Some(HIGH_WIND), Some(NORTH_WIND)),
+            RipCurrentRisk::Low
+        );
+        // Without a wave height only onshore wind raises the risk
+        assert_eq!(
+            profile.estimate_rip_current_risk(None, Some(HIGH_WIND), Some(NORTH_WIND)),
+            RipCurrentRisk::Moderate
+        );
+        assert_eq!(
+            profile.estimate_rip_current_risk(None, Some(HIGH_WIND), Some(SOUTH_WIND)),
+            RipCurrentRisk::Low
+        );
+        assert_eq!(
+            profile.estimate_rip_current_risk(Some(HIGH_WAVES), None, None),
+            RipCurrentRisk::Moderate
+        );
     }
 }
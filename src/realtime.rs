@@ -1,233 +1,527 @@
 use super::Spot;
 use crate::{
-    AppState,
-    utils::{
-        convert_celsius_to_fahrenheit, convert_meter_per_second_to_miles_per_hour,
-        convert_meter_to_feet, redis_utils,
-    },
+    AppState, Conditions, Length, NdbcObservation, NdbcSpectralSummary, QualityReason,
+    RipCurrentRisk, Score, Speed, SurfZoneForecast, Temperature, UnitLabels, Units,
+    observations_path, spectral_summary_path,
+    utils::{redis_utils, round_to_two_decimals},
 };
 
-use anyhow::bail;
-#[cfg(not(feature = "mock-time"))]
-use chrono::TimeDelta;
-use chrono::{DateTime, TimeZone, Utc};
-use chrono_tz::US::Central;
+use anyhow::{anyhow, bail};
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
+use chrono::{DateTime, TimeDelta, Utc};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
 #[derive(serde::Serialize)]
 pub struct Realtime {
     pub as_of: String,
-    pub wind_direction: u32,
-    pub wind_speed: String,
-    pub gusts: String,
-    pub water_temp: String,
-    pub air_temp: String,
+    /// RFC 3339 time of the oldest reading used
+    pub observed_at: String,
+    /// How old the oldest reading used is
+    pub age_minutes: i64,
+    /// Whether no station has reported within the spot's max age
+    pub stale: bool,
+    /// `None` when no station reported the wind, as with the other readings
+    pub wind_direction: Option<u32>,
+    pub wind_speed: Option<f64>,
+    pub gusts: Option<f64>,
+    /// Descriptions of the wind speeds when they're Beaufort forces
+    pub wind_description: Option<&'static str>,
+    pub gusts_description: Option<&'static str>,
+    pub water_temp: Option<f64>,
+    pub air_temp: Option<f64>,
     pub quality_color: &'static str,
     pub quality_text: &'static str,
-    pub wave_height: Option<String>,
+    pub quality_reason: QualityReason,
+    pub score: Score,
+    pub wave_height: Option<f64>,
     pub wave_period: Option<u8>,
     pub wave_direction: Option<u16>,
-    pub loaded_from_fallback: bool,
+    /// Swell and wind waves, when the spot has a bouy publishing its spectral summary
+    pub wave_components: Option<WaveComponents>,
+    pub rip_current: RipCurrent,
+    pub sources: Sources,
+    /// The units the values are in
+    pub units: UnitLabels,
+}
+
+/// The rip current risk at the spot's beach.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct RipCurrent {
+    pub risk: RipCurrentRisk,
+    pub source: RipCurrentSource,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RipCurrentSource {
+    /// The NWS surf zone forecast for the spot's beach zone
+    Nws,
+    /// Estimated from the onshore wind and wave height
+    Estimated,
+}
+
+/// The waves split into swell and wind waves, telling clean swell from chop.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct WaveComponents {
+    pub swell: WaveComponent,
+    pub wind_waves: WaveComponent,
+    /// e.g. "SWELL", "AVERAGE", "STEEP" or "VERY_STEEP"
+    pub steepness: Option<String>,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct WaveComponent {
+    pub height: Option<f64>,
+    pub period: Option<f64>,
+    /// Where the waves are headed, like `Realtime::wave_direction`
+    pub direction: Option<u16>,
+}
+
+impl WaveComponent {
+    fn new(height: Option<f64>, period: Option<f64>, direction: Option<f64>, units: Units) -> Self {
+        Self {
+            height: height.map(|v| round_to_two_decimals(units.height(Length::from_meters(v)))),
+            period,
+            direction: direction.map(headed_toward),
+        }
+    }
+}
+
+/// Bouys report the direction waves come from, display where they're headed.
+pub(crate) fn headed_toward(from: f64) -> u16 {
+    (from + 180.0).rem_euclid(360.0) as u16
+}
+
+impl WaveComponents {
+    fn new(summary: &NdbcSpectralSummary, units: Units) -> Self {
+        Self {
+            swell: WaveComponent::new(
+                summary.swell_height,
+                summary.swell_period,
+                summary.swell_direction,
+                units,
+            ),
+            wind_waves: WaveComponent::new(
+                summary.wind_wave_height,
+                summary.wind_wave_period,
+                summary.wind_wave_direction,
+                units,
+            ),
+            steepness: summary.steepness.clone(),
+        }
+    }
+}
+
+/// The NDBC station each field was taken from, `None` when no station reported it.
+#[derive(serde::Serialize, Debug, Default, PartialEq)]
+pub struct Sources {
+    pub wind: Option<Source>,
+    pub gusts: Option<Source>,
+    pub waves: Option<Source>,
+    pub water_temp: Option<Source>,
+    pub air_temp: Option<Source>,
+}
+
+/// The station a field was taken from and how old its reading is.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct Source {
+    pub station: &'static str,
+    pub age_minutes: i64,
+}
+
+/// The observations of one NDBC station, newest first.
+struct Station {
+    id: &'static str,
+    observations: Vec<NdbcObservation>,
+}
+
+/// How much older a reading from a preferred station can be than the freshest reading and
+/// still be used. Stations report at different minutes past the hour.
+const PREFERRED_STATION_WINDOW: TimeDelta = TimeDelta::hours(1);
+
+/// How long a surf zone forecast is used for, offices that issue them do so every morning.
+const SURF_ZONE_FORECAST_MAX_AGE: TimeDelta = TimeDelta::hours(24);
+
+/// The quality computed for the latest cached realtime data.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct CachedQuality {
+    pub quality_color: String,
+    pub quality_text: String,
+}
+
+fn cache_key(spot: &Spot, units: Units) -> String {
+    format!("realtime-{}-{units}", spot.name)
 }
 
 impl Realtime {
-    pub async fn try_get_string(spot: Arc<Spot>, state: Arc<AppState>) -> anyhow::Result<String> {
-        if let Some(data) =
-            redis_utils::get(&format!("realtime-{}", spot.name), &state.redis_pool).await
-        {
+    pub async fn try_get_string(
+        spot: Arc<Spot>,
+        state: Arc<AppState>,
+        units: Units,
+    ) -> anyhow::Result<String> {
+        if let Some(data) = redis_utils::get(&cache_key(&spot, units), &state.redis_pool).await {
             tracing::info!("redis cache hit!");
             return Ok(data);
         }
 
-        let data = Self::try_get(spot.clone(), state.realtime_url).await?;
+        let data = Self::try_get(
+            spot.clone(),
+            state.realtime_url,
+            state.forecast_url,
+            &state.redis_pool,
+            units,
+        )
+        .await?;
         let data = serde_json::to_string(&data)?;
 
-        redis_utils::set(&format!("realtime-{}", spot.name), &data, &state.redis_pool).await?;
+        redis_utils::cache(&cache_key(&spot, units), &data, &state.redis_pool).await;
 
         Ok(data)
     }
 
-    pub async fn try_get(spot: Arc<Spot>, realtime_url: &'static str) -> anyhow::Result<Self> {
-        const FALLBACK_BOUY: &str = "/data/realtime2/45007.txt";
+    /// Gets the quality of each spot from the Redis cache without fetching new data. The
+    /// quality is the same whatever the units, only the default units are looked up.
+    pub async fn get_cached_qualities(
+        spots: &[&Spot],
+        state: &AppState,
+    ) -> Vec<Option<CachedQuality>> {
+        let keys = spots
+            .iter()
+            .map(|spot| cache_key(spot, Units::default()))
+            .collect::<Vec<_>>();
+
+        redis_utils::get_many(&keys, &state.redis_pool)
+            .await
+            .into_iter()
+            .map(|data| serde_json::from_str(&data?).ok())
+            .collect()
+    }
 
-        let mut from_fallback = false;
+    pub async fn try_get(
+        spot: Arc<Spot>,
+        realtime_url: &'static str,
+        forecast_url: &str,
+        redis_pool: &Pool<RedisConnectionManager>,
+        units: Units,
+    ) -> anyhow::Result<Self> {
+        let (stations, surf_zone_forecast) = tokio::join!(
+            Self::try_get_stations(&spot, realtime_url),
+            Self::get_surf_zone_forecast(&spot, forecast_url, redis_pool)
+        );
+        let stations = stations?;
 
-        // Seems as though bouy data is removed from noaa after it gets stale enough with no new
-        // information. Check if there was an error and then try the fallback.
-        let data = match Self::get_latest_data(&spot, realtime_url).await {
-            Ok(data) => data,
-            _ => {
-                from_fallback = true;
-                Self::get_fallback_data(&spot, realtime_url, FALLBACK_BOUY).await?
+        #[cfg(not(feature = "mock-time"))]
+        let now = Utc::now();
+        // When mocking time, the newest observation was just made
+        #[cfg(feature = "mock-time")]
+        let now = stations
+            .iter()
+            .filter_map(|station| station.observations.first())
+            .map(|o| o.observed_at)
+            .max()
+            .unwrap_or_default();
+
+        let max_age = TimeDelta::minutes(spot.max_age_minutes);
+        let (latest, sources) = Self::merge(&stations, now, max_age)?;
+
+        let spectral_summary = match &sources.waves {
+            Some(source) if spot.has_bouy => {
+                Self::get_spectral_summary(source.station, realtime_url).await
             }
+            _ => None,
         };
 
-        let mut loaded_from_fallback = !spot.has_bouy || from_fallback;
+        Self::try_from_observation(
+            &latest,
+            spectral_summary.as_ref(),
+            surf_zone_forecast.as_ref(),
+            sources,
+            &spot,
+            now,
+            units,
+        )
+    }
 
-        let latest = data.lines().collect::<Vec<_>>();
-        let line = latest.get(2).unwrap();
+    /// Gets the surf zone forecast when the spot has a beach zone. The risk is estimated
+    /// without it so failing to load it is only logged.
+    async fn get_surf_zone_forecast(
+        spot: &Spot,
+        forecast_url: &str,
+        redis_pool: &Pool<RedisConnectionManager>,
+    ) -> Option<SurfZoneForecast> {
+        spot.beach_zone?;
+
+        match SurfZoneForecast::try_get(spot, forecast_url, redis_pool).await {
+            Ok(forecast) => forecast,
+            Err(e) => {
+                warn!(
+                    "Unable to load the surf zone forecast for {}: {e}",
+                    spot.name
+                );
+                None
+            }
+        }
+    }
 
-        let (as_of, measurements) = line.split_at(16);
+    /// Gets the latest spectral summary of the bouy, which isn't needed for the rest of
+    /// the realtime data so failing to load it is only logged.
+    async fn get_spectral_summary(
+        station: &str,
+        realtime_url: &str,
+    ) -> Option<NdbcSpectralSummary> {
+        match Self::get_data(&spectral_summary_path(station), realtime_url)
+            .await
+            .and_then(|data| NdbcSpectralSummary::try_parse_latest(&data))
+        {
+            Ok(summary) => Some(summary),
+            Err(e) => {
+                warn!("Unable to load the spectral summary of station {station}: {e}");
+                None
+            }
+        }
+    }
 
-        let as_of = Self::parse_as_of(as_of)?;
+    /// Fetches every station of the spot concurrently. Seems as though bouy data is removed
+    /// from noaa after it gets stale enough, so stations that fail are left out and it's only
+    /// an error when none of them load.
+    async fn try_get_stations(
+        spot: &Spot,
+        realtime_url: &'static str,
+    ) -> anyhow::Result<Vec<Station>> {
+        let handles = spot
+            .stations
+            .iter()
+            .map(|&id| {
+                tokio::spawn(async move {
+                    let data = Self::get_data(&observations_path(id), realtime_url).await?;
+                    NdbcObservation::try_parse_all(&data)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut stations = Vec::with_capacity(handles.len());
+        let mut first_error = None;
+        for (&id, handle) in spot.stations.iter().zip(handles) {
+            match handle.await? {
+                Ok(observations) => stations.push(Station { id, observations }),
+                Err(e) => {
+                    warn!("Unable to load station {id}: {e}");
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
 
-        // Check if the bouy data is older than a day, if so fallback to other path. And only if
-        // the data isn't already from the fallback.
-        #[cfg(not(feature = "mock-time"))]
-        let get_from_fallback = Utc::now() - as_of > TimeDelta::days(1) && !from_fallback;
-        // When mocking time, ignore how old the data is, it's being mocked
-        #[cfg(feature = "mock-time")]
-        let get_from_fallback = false;
-
-        if get_from_fallback {
-            let data = Self::get_fallback_data(&spot, realtime_url, FALLBACK_BOUY).await?;
-            loaded_from_fallback = true;
-            let latest = data.lines().collect::<Vec<_>>();
-            let line = latest.get(2).unwrap();
-
-            let (as_of, measurements) = line.split_at(16);
-            let as_of = Self::parse_as_of(as_of)?;
-            return Self::parse_data(
-                measurements,
-                &latest,
-                &as_of,
-                &spot,
-                realtime_url,
-                loaded_from_fallback,
-            )
-            .await;
+        match first_error {
+            Some(e) if stations.is_empty() => Err(e),
+            _ => Ok(stations),
         }
+    }
 
-        Self::parse_data(
-            measurements,
-            &latest,
-            &as_of,
-            &spot,
-            realtime_url,
-            loaded_from_fallback,
-        )
-        .await
+    /// Merges the stations into a single observation, taking each field from the freshest
+    /// station reporting it within `max_age`. A station earlier in the list is preferred over
+    /// a fresher one when its reading is within `PREFERRED_STATION_WINDOW` of the freshest.
+    /// When no station has reported anything within `max_age` the last readings are used, so
+    /// they can be shown as stale. The merged observation is as old as the oldest reading used.
+    fn merge(
+        stations: &[Station],
+        now: DateTime<Utc>,
+        max_age: TimeDelta,
+    ) -> anyhow::Result<(NdbcObservation, Sources)> {
+        let cutoff = now - max_age;
+        let reported = stations.iter().any(|station| {
+            station
+                .observations
+                .first()
+                .is_some_and(|o| o.observed_at >= cutoff)
+        });
+        let cutoff = if reported {
+            cutoff
+        } else {
+            DateTime::<Utc>::MIN_UTC
+        };
+
+        let wind = Self::freshest(stations, cutoff, |o| o.wind_speed.is_some());
+        let gusts = Self::freshest(stations, cutoff, |o| o.gust.is_some());
+        let waves = Self::freshest(stations, cutoff, |o| o.wave_height.is_some());
+        let water_temp = Self::freshest(stations, cutoff, |o| o.water_temp.is_some());
+        let air_temp = Self::freshest(stations, cutoff, |o| o.air_temp.is_some());
+
+        let observed_at = [&wind, &gusts, &waves, &water_temp, &air_temp]
+            .into_iter()
+            .flatten()
+            .map(|(station, index)| station.observations[*index].observed_at)
+            .min()
+            .ok_or(anyhow!("No station has any observations"))?;
+
+        let field = |reading: &Option<(&Station, usize)>,
+                     get: fn(&NdbcObservation) -> Option<f64>| {
+            reading.and_then(|(station, index)| get(&station.observations[index]))
+        };
+
+        let latest = NdbcObservation {
+            observed_at,
+            wind_direction: field(&wind, |o| o.wind_direction),
+            wind_speed: field(&wind, |o| o.wind_speed),
+            gust: field(&gusts, |o| o.gust),
+            wave_height: field(&waves, |o| o.wave_height),
+            dominant_period: field(&waves, |o| o.dominant_period),
+            average_period: field(&waves, |o| o.average_period),
+            // Sometimes bouys only update the wave direction every third hour,
+            // this attempts to fallback to earlier readings.
+            wave_direction: waves.and_then(|(station, index)| {
+                station.observations[index..]
+                    .iter()
+                    .take(3)
+                    .find_map(|o| o.wave_direction)
+            }),
+            pressure: field(&wind, |o| o.pressure),
+            air_temp: field(&air_temp, |o| o.air_temp),
+            water_temp: field(&water_temp, |o| o.water_temp),
+            dewpoint: field(&air_temp, |o| o.dewpoint),
+        };
+
+        let source = |reading: Option<(&Station, usize)>| {
+            reading.map(|(station, index)| Source {
+                station: station.id,
+                age_minutes: (now - station.observations[index].observed_at).num_minutes(),
+            })
+        };
+        let sources = Sources {
+            wind: source(wind),
+            gusts: source(gusts),
+            waves: source(waves),
+            water_temp: source(water_temp),
+            air_temp: source(air_temp),
+        };
+
+        Ok((latest, sources))
     }
 
-    async fn parse_data(
-        measurements: &str,
-        latest: &[&str],
-        as_of: &DateTime<Utc>,
+    /// Finds the station, and the index of its observation, with the freshest reading of a
+    /// field made since `cutoff`.
+    fn freshest(
+        stations: &[Station],
+        cutoff: DateTime<Utc>,
+        has_field: impl Fn(&NdbcObservation) -> bool,
+    ) -> Option<(&Station, usize)> {
+        let readings = stations
+            .iter()
+            .filter_map(|station| {
+                station
+                    .observations
+                    .iter()
+                    .take_while(|o| o.observed_at >= cutoff)
+                    .position(&has_field)
+                    .map(|index| (station, index))
+            })
+            .collect::<Vec<_>>();
+
+        let observed_at =
+            |(station, index): &(&Station, usize)| station.observations[*index].observed_at;
+        let freshest = readings.iter().map(observed_at).max()?;
+
+        readings
+            .into_iter()
+            .find(|reading| freshest - observed_at(reading) <= PREFERRED_STATION_WINDOW)
+    }
+
+    fn try_from_observation(
+        latest: &NdbcObservation,
+        spectral_summary: Option<&NdbcSpectralSummary>,
+        surf_zone_forecast: Option<&SurfZoneForecast>,
+        sources: Sources,
         spot: &Spot,
-        realtime_url: &str,
-        loaded_from_fallback: bool,
+        now: DateTime<Utc>,
+        units: Units,
     ) -> anyhow::Result<Self> {
-        // MID Lake bouy is in the water yeat round
-        const FALLBACK_BOUY: &str = "/data/realtime2/45007.txt";
-
-        let as_of = as_of
-            .with_timezone(&Central)
-            .to_rfc2822()
-            .split(" -")
-            .next()
-            .unwrap()
+        // Local time without the offset, e.g. "Mon, 10 Jun 2024 16:50:00"
+        let as_of = latest
+            .observed_at
+            .with_timezone(&spot.location.time_zone)
+            .format("%a, %d %b %Y %H:%M:%S")
             .to_string();
 
-        let mut measurements = measurements.split_whitespace();
-        let wind_direction = measurements.next().unwrap().parse().unwrap_or(0);
+        let age_minutes = (now - latest.observed_at).num_minutes();
+        let stale = age_minutes > spot.max_age_minutes;
+        if stale {
+            warn!(
+                "No station at {} has reported in {age_minutes} minutes",
+                spot.name
+            );
+        }
 
-        let wind_speed = convert_meter_per_second_to_miles_per_hour(measurements.next().unwrap());
-        let gusts = convert_meter_per_second_to_miles_per_hour(measurements.next().unwrap());
+        let wind_direction = latest.wind_direction.map(|v| v as u32);
 
-        let wave_height = Self::parse_wave_height(measurements.next().unwrap_or(""));
-        let wave_period = measurements.next().unwrap().parse().ok();
+        let wind_speed = latest.wind_speed.map(Speed::from_meters_per_second);
+        let gusts = latest.gust.map(Speed::from_meters_per_second);
+        let wave_height = latest.wave_height.map(Length::from_meters);
+        let wave_period = latest.dominant_period.map(|v| v as u8);
 
-        let _ = measurements.next();
+        let swell_direction = latest.wave_direction.map(|v| v as u16);
+        let wave_direction = latest.wave_direction.map(headed_toward);
 
-        // Sometimes bouys only update the wave direction every third hour,
-        // this attempts to fallback to earlier readings.
-        let wave_direction = match measurements
-            .next()
-            .unwrap()
-            .parse::<u16>()
-            .ok()
-            .map(|v| v + 180)
-        {
-            Some(v) => Some(v),
-            None => match Self::get_wave_direction(latest, 1) {
-                Some(v) => Some(v),
-                None => Self::get_wave_direction(latest, 2),
-            },
-        };
+        let temperature =
+            |v: Option<f64>| v.map(|v| units.temperature(Temperature::from_celsius(v)).round());
 
-        let _ = measurements.next();
-
-        let air_temp =
-            convert_celsius_to_fahrenheit(measurements.next().unwrap().parse().unwrap_or(0.0));
-
-        let raw_water_temp = measurements.next().unwrap_or("MM");
-
-        let water_temp = if raw_water_temp == "MM" {
-            info!("fetching fallback bouy data for water temp");
-            let bouy_data = reqwest::get(format!("{realtime_url}{FALLBACK_BOUY}"))
-                .await?
-                .text()
-                .await?;
-
-            // Start at row two to get past the table headers
-            let mut row = 2;
-            // Loop until a valid value is found.
-            while bouy_data
-                .lines()
-                .nth(row)
-                .unwrap_or("")
-                .split_whitespace()
-                .nth(14)
-                .unwrap_or("0.0")
-                .parse::<f64>()
-                .is_err()
-            {
-                row += 1;
-            }
-
-            convert_celsius_to_fahrenheit(
-                bouy_data
-                    .lines()
-                    .nth(row)
-                    .unwrap_or("")
-                    .split_whitespace()
-                    .nth(14)
-                    .unwrap_or("0.0")
-                    .parse()
-                    .unwrap(),
-            )
-        } else {
-            convert_celsius_to_fahrenheit(raw_water_temp.parse().unwrap_or(0.0))
+        // Quality is rated in feet and mph whatever the display units
+        let conditions = Conditions {
+            wave_height: wave_height.map(|v| round_to_two_decimals(v.feet())),
+            wave_period: wave_period.map(f64::from),
+            wave_direction: swell_direction.map(f64::from),
+            wind_speed: wind_speed.map(|v| v.miles_per_hour().round()),
+            wind_direction: wind_direction.map(f64::from),
+        };
+        let rating = spot.location.get_quality(&conditions);
+        let score = spot.location.get_score(&conditions, &rating);
+
+        let issued_risk = surf_zone_forecast
+            .filter(|forecast| now - forecast.issued <= SURF_ZONE_FORECAST_MAX_AGE)
+            .zip(spot.beach_zone)
+            .and_then(|(forecast, zone)| forecast.rip_current_risk(zone));
+        let rip_current = match issued_risk {
+            Some(risk) => RipCurrent {
+                risk,
+                source: RipCurrentSource::Nws,
+            },
+            None => RipCurrent {
+                risk: spot.location.quality_profile.estimate_rip_current_risk(
+                    conditions.wave_height,
+                    conditions.wind_speed,
+                    conditions.wind_direction,
+                ),
+                source: RipCurrentSource::Estimated,
+            },
         };
-
-        let wave_quality = spot.location.get_quality(
-            wave_height
-                .clone()
-                .unwrap_or("99.0".to_string())
-                .parse()
-                .unwrap(),
-            wind_speed.parse().unwrap(),
-            wind_direction as f64,
-        );
 
         Ok(Self {
-            air_temp,
+            air_temp: temperature(latest.air_temp),
             as_of,
+            observed_at: latest.observed_at.to_rfc3339(),
+            age_minutes,
+            stale,
             wind_direction,
-            wind_speed,
-            gusts,
-            water_temp,
-            quality_text: wave_quality.0,
-            quality_color: wave_quality.1,
-            wave_height,
+            wind_speed: wind_speed.map(|v| units.speed(v).round()),
+            gusts: gusts.map(|v| units.speed(v).round()),
+            wind_description: wind_speed.and_then(|v| units.describe_speed(v)),
+            gusts_description: gusts.and_then(|v| units.describe_speed(v)),
+            water_temp: temperature(latest.water_temp),
+            quality_text: score.label,
+            quality_color: score.color,
+            quality_reason: rating.reason,
+            score,
+            wave_height: wave_height.map(|v| round_to_two_decimals(units.height(v))),
             wave_period,
             wave_direction,
-            loaded_from_fallback,
+            wave_components: spectral_summary.map(|summary| WaveComponents::new(summary, units)),
+            rip_current,
+            sources,
+            units: units.labels(),
         })
     }
 
-    async fn get_data(path: &str, realtime_url: &str) -> Result<String, anyhow::Error> {
+    pub(crate) async fn get_data(path: &str, realtime_url: &str) -> Result<String, anyhow::Error> {
         const RETRY: u8 = 2;
         for _ in 0..RETRY {
             let response = reqwest::get(format!("{realtime_url}{path}")).await?;
@@ -244,57 +538,185 @@ impl Realtime {
         error!("Non 200 response from NOAA realtime");
         bail!("Non 200 response from NOAA realtime");
     }
+}
 
-    async fn get_latest_data(spot: &Spot, realtime_url: &str) -> Result<String, anyhow::Error> {
-        Self::get_data(spot.realtime_path, realtime_url).await
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    /// Checks if the bouy has a fallback available, otherwise uses the path provided.
-    async fn get_fallback_data(
-        spot: &Spot,
-        realtime_url: &str,
-        fallback_url: &str,
-    ) -> Result<String, anyhow::Error> {
-        if let Some(path) = spot.fallback_realtime_path {
-            Self::get_data(path, realtime_url).await
-        } else {
-            Self::get_data(fallback_url, realtime_url).await
+    fn station(id: &'static str, data: &str) -> Station {
+        Station {
+            id,
+            observations: NdbcObservation::try_parse_all(data).unwrap(),
         }
     }
 
-    fn parse_wave_height(wave_height: &str) -> Option<String> {
-        if let Ok(v) = wave_height.parse::<f64>() {
-            Some(format!("{:.2}", convert_meter_to_feet(v)))
-        } else {
-            None
-        }
+    /// When the newest fixture reading was made.
+    fn now() -> DateTime<Utc> {
+        "2024-06-10T22:00:00Z".parse().unwrap()
     }
 
-    fn parse_as_of(as_of: &str) -> anyhow::Result<DateTime<Utc>> {
-        let as_of = as_of.trim().split(' ').collect::<Vec<_>>();
-        let as_of = Utc
-            .with_ymd_and_hms(
-                as_of.first().unwrap().parse::<i32>().unwrap(),
-                as_of.get(1).unwrap().parse::<u32>().unwrap(),
-                as_of.get(2).unwrap().parse::<u32>().unwrap(),
-                as_of.get(3).unwrap().parse::<u32>().unwrap(),
-                as_of.get(4).unwrap().parse::<u32>().unwrap(),
-                00,
-            )
-            .unwrap();
-
-        Ok(as_of)
+    fn max_age() -> TimeDelta {
+        TimeDelta::days(1)
     }
 
-    fn get_wave_direction(latest: &[&str], offset: usize) -> Option<u16> {
-        latest
-            .get(2 + offset)
-            .unwrap()
-            .split_whitespace()
-            .nth(11)
-            .unwrap()
-            .parse::<u16>()
-            .ok()
-            .map(|v| v + 180)
+    fn station_of(source: &Option<Source>) -> Option<&'static str> {
+        source.as_ref().map(|source| source.station)
+    }
+
+    fn atwater() -> Vec<Station> {
+        vec![
+            station("45013", include_str!("../tests/fixtures/ndbc/45013.txt")),
+            station("MLWW3", include_str!("../tests/fixtures/ndbc/MLWW3.txt")),
+            station("45007", include_str!("../tests/fixtures/ndbc/45007.txt")),
+        ]
+    }
+
+    #[test]
+    fn wave_components_split_swell_from_wind_waves() {
+        let summary = NdbcSpectralSummary::try_parse_latest(include_str!(
+            "../tests/fixtures/ndbc/45007.spec"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            WaveComponents::new(&summary, Units::IMPERIAL),
+            WaveComponents {
+                swell: WaveComponent {
+                    height: Some(1.64),
+                    period: Some(8.3),
+                    direction: Some(157),
+                },
+                wind_waves: WaveComponent {
+                    height: Some(1.31),
+                    period: Some(4.5),
+                    direction: Some(202),
+                },
+                steepness: Some("AVERAGE".to_string()),
+            }
+        );
+        assert_eq!(
+            WaveComponents::new(&summary, Units::METRIC).swell.height,
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn wave_directions_stay_on_the_compass() {
+        assert_eq!(headed_toward(15.0), 195);
+        assert_eq!(headed_toward(180.0), 0);
+        assert_eq!(headed_toward(337.5), 157);
+    }
+
+    #[test]
+    fn merge_prefers_earlier_stations_with_recent_readings() {
+        let bouy = || Source {
+            station: "45013",
+            age_minutes: 10,
+        };
+        let (latest, sources) = Realtime::merge(&atwater(), now(), max_age()).unwrap();
+
+        assert_eq!(
+            sources,
+            Sources {
+                wind: Some(bouy()),
+                gusts: Some(bouy()),
+                waves: Some(bouy()),
+                water_temp: Some(bouy()),
+                air_temp: Some(bouy()),
+            }
+        );
+        assert_eq!(latest.observed_at.to_rfc3339(), "2024-06-10T21:50:00+00:00");
+        assert_eq!(latest.wind_speed, Some(5.0));
+    }
+
+    #[test]
+    fn merge_takes_each_field_from_the_freshest_station_reporting_it() {
+        let mut stations = atwater();
+        // The bouy stopped reporting waves and water temp hours ago
+        stations[0] = station(
+            "45013",
+            "#YY  MM DD hh mm WDIR WSPD GST  WVHT   DPD  MWD  ATMP  WTMP\n\
+             #yr  mo dy hr mn degT m/s  m/s     m   sec degT  degC  degC\n\
+             2024 06 10 21 50  30  5.0  7.0    MM    MM   MM  15.1    MM\n\
+             2024 06 10 17 50  30  5.0  7.0   0.4     4   40  15.1  13.4\n",
+        );
+
+        let (latest, sources) = Realtime::merge(&stations, now(), max_age()).unwrap();
+
+        assert_eq!(station_of(&sources.wind), Some("45013"));
+        assert_eq!(station_of(&sources.waves), Some("45007"));
+        assert_eq!(station_of(&sources.water_temp), Some("45007"));
+        assert_eq!(latest.wave_height, Some(0.7));
+        assert_eq!(latest.wave_direction, Some(15.0));
+        assert_eq!(latest.water_temp, Some(10.2));
+        // As old as the waves from 45007, not the fresher wind
+        assert_eq!(latest.observed_at.to_rfc3339(), "2024-06-10T21:40:00+00:00");
+    }
+
+    #[test]
+    fn merge_falls_back_to_earlier_wave_directions() {
+        let stations = vec![station(
+            "45013",
+            "#YY  MM DD hh mm WVHT  DPD  MWD\n\
+             #yr  mo dy hr mn    m  sec degT\n\
+             2024 06 10 21 50  0.4    4   MM\n\
+             2024 06 10 21 40   MM   MM   MM\n\
+             2024 06 10 21 30  0.4    4   42\n",
+        )];
+
+        let (latest, _) = Realtime::merge(&stations, now(), max_age()).unwrap();
+
+        assert_eq!(latest.wave_height, Some(0.4));
+        assert_eq!(latest.wave_direction, Some(42.0));
+    }
+
+    #[test]
+    fn merge_leaves_unreported_fields_without_a_source() {
+        let stations = vec![station(
+            "MLWW3",
+            include_str!("../tests/fixtures/ndbc/MLWW3.txt"),
+        )];
+
+        let (latest, sources) = Realtime::merge(&stations, now(), max_age()).unwrap();
+
+        assert_eq!(station_of(&sources.wind), Some("MLWW3"));
+        assert_eq!(station_of(&sources.waves), None);
+        assert_eq!(station_of(&sources.water_temp), None);
+        assert_eq!(latest.wave_height, None);
+    }
+
+    #[test]
+    fn merge_skips_readings_older_than_the_cutoff() {
+        let stations = vec![
+            // The bouy's last waves are days old
+            station(
+                "45013",
+                "#YY  MM DD hh mm WDIR WSPD GST  WVHT   DPD  MWD  ATMP  WTMP\n\
+                 #yr  mo dy hr mn degT m/s  m/s     m   sec degT  degC  degC\n\
+                 2024 06 10 21 50  MM   MM   MM    MM    MM   MM    MM    MM\n\
+                 2024 06 07 17 50  30  5.0  7.0   0.4     4   40  15.1  13.4\n",
+            ),
+            station("MLWW3", include_str!("../tests/fixtures/ndbc/MLWW3.txt")),
+        ];
+
+        let (latest, sources) = Realtime::merge(&stations, now(), max_age()).unwrap();
+
+        assert_eq!(station_of(&sources.wind), Some("MLWW3"));
+        assert_eq!(station_of(&sources.waves), None);
+        assert_eq!(station_of(&sources.water_temp), None);
+        assert_eq!(latest.wave_height, None);
+        assert_eq!(latest.wind_speed, Some(4.6));
+    }
+
+    #[test]
+    fn merge_uses_the_last_readings_when_every_station_is_down() {
+        let now = "2024-06-12T00:00:00Z".parse().unwrap();
+
+        let (latest, sources) = Realtime::merge(&atwater(), now, max_age()).unwrap();
+
+        assert_eq!(station_of(&sources.wind), Some("45013"));
+        assert_eq!(sources.wind.unwrap().age_minutes, 26 * 60 + 10);
+        assert_eq!(latest.wind_speed, Some(5.0));
     }
 }
@@ -0,0 +1,50 @@
+use super::AppError;
+use crate::{AppState, Comparison, SpotsParam, SpotsQuery, TEMPLATES, UnitsQuery};
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::header,
+    response::{Html, IntoResponse},
+};
+use serde_json::json;
+use std::sync::Arc;
+
+pub async fn compare(
+    selected_spots: SpotsQuery,
+    UnitsQuery(units): UnitsQuery,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Comparison>, AppError> {
+    Ok(Json(
+        Comparison::try_get(selected_spots.0, state, units).await?,
+    ))
+}
+
+/// Handler to return the comparison of several spots as a page, rendered
+/// up front from the same data as the api.
+pub async fn compare_page(
+    State(state): State<Arc<AppState>>,
+    selected_spots: Query<SpotsParam>,
+    UnitsQuery(units): UnitsQuery,
+) -> Result<impl IntoResponse, AppError> {
+    let mut context = tera::Context::new();
+
+    let spots = selected_spots.get_spots(&state.spots)?;
+
+    context.insert("spot", &json!({ "name": "Compare" }));
+    context.insert("breaks", &state.breaks);
+    context.insert("units", &units.labels());
+    #[cfg(debug_assertions)]
+    context.insert("live_reload", &true);
+    #[cfg(not(debug_assertions))]
+    context.insert("live_reload", &false);
+
+    context.insert(
+        "comparison",
+        &Comparison::try_get(spots, state.clone(), units).await?,
+    );
+
+    let html = Html(TEMPLATES.render("compare.html", &context)?);
+
+    // Remember the choice so the page keeps it without the query param.
+    Ok(([(header::SET_COOKIE, units.cookie())], html))
+}
@@ -1,13 +1,33 @@
 use super::AppError;
-use crate::{AppState, Forecast, SpotQuery};
-use axum::{Json, extract::State};
+use crate::{AppState, Forecast, Sessions, SpotQuery, UnitsQuery};
+use axum::{Json, extract::State, http::header, response::IntoResponse};
 use std::sync::Arc;
 
 pub async fn forecast(
     selected_spot: SpotQuery,
+    UnitsQuery(units): UnitsQuery,
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<Forecast>, AppError> {
     Ok(Json(
-        Forecast::try_get(&selected_spot.0.into(), state.forecast_url).await?,
+        Forecast::try_get(
+            &selected_spot.0,
+            state.forecast_url,
+            state.glcfs_url,
+            &state.redis_pool,
+            units,
+        )
+        .await?,
+    ))
+}
+
+/// The upcoming daylight sessions of good or fair to good conditions.
+pub async fn forecast_sessions(
+    selected_spot: SpotQuery,
+    UnitsQuery(units): UnitsQuery,
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok((
+        [(header::CONTENT_TYPE, "application/json")],
+        Sessions::try_get_string(&selected_spot.0, state, units).await?,
     ))
 }
@@ -1,8 +1,9 @@
 use super::AppError;
-use crate::{AppState, Realtime, Spot, SpotParam, TEMPLATES};
+use crate::{AppState, Realtime, Spot, SpotParam, TEMPLATES, UnitsQuery};
 use axum::{
     extract::{Query, State},
-    response::Html,
+    http::header,
+    response::{Html, IntoResponse},
 };
 use std::sync::Arc;
 
@@ -12,19 +13,29 @@ use std::sync::Arc;
 pub async fn glimpse(
     State(state): State<Arc<AppState>>,
     selected_spot: Query<SpotParam>,
-) -> Result<Html<String>, AppError> {
+    UnitsQuery(units): UnitsQuery,
+) -> Result<impl IntoResponse, AppError> {
     let mut context = tera::Context::new();
 
-    let spot: Arc<Spot> = Arc::new(selected_spot.0.into());
+    let spot: Arc<Spot> = selected_spot.get_spot(&state.spots)?;
 
     context.insert("spot", &*spot);
     context.insert("breaks", &state.breaks);
+    context.insert("units", &units.labels());
     #[cfg(debug_assertions)]
     context.insert("live_reload", &true);
     #[cfg(not(debug_assertions))]
     context.insert("live_reload", &false);
 
-    match Realtime::try_get(spot, state.realtime_url).await {
+    match Realtime::try_get(
+        spot,
+        state.realtime_url,
+        state.forecast_url,
+        &state.redis_pool,
+        units,
+    )
+    .await
+    {
         Ok(latest) => {
             context.insert("as_of", &latest.as_of);
             context.insert("wind_direction", &latest.wind_direction);
@@ -37,6 +48,9 @@ pub async fn glimpse(
             context.insert("quality_color", &latest.quality_color);
             context.insert("wave_period", &latest.wave_period);
             context.insert("wave_direction", &latest.wave_direction);
+            context.insert("rip_current_risk", &latest.rip_current.risk);
+            context.insert("rip_current_source", &latest.rip_current.source);
+            context.insert("rip_current_color", latest.rip_current.risk.color());
         }
         Err(e) => {
             context.insert("error", &e.to_string());
@@ -46,11 +60,14 @@ pub async fn glimpse(
         }
     }
 
-    match TEMPLATES.render("glimpse.html", &context) {
-        Ok(s) => Ok(Html(s)),
+    let html = match TEMPLATES.render("glimpse.html", &context) {
+        Ok(s) => Html(s),
         Err(e) => {
             tracing::error!("{:?}", e);
-            Ok(Html("<html>error</html>".to_string()))
+            Html("<html>error</html>".to_string())
         }
-    }
+    };
+
+    // Remember the choice so the page keeps it without the query param.
+    Ok(([(header::SET_COOKIE, units.cookie())], html))
 }
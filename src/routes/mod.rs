@@ -1,17 +1,23 @@
+mod compare;
 mod forecast;
 mod glimpse;
 mod handle_404;
 mod health_check;
 mod realtime;
 mod root;
+mod spots;
 #[cfg(debug_assertions)]
 mod watch;
+mod water_level;
 
-pub use forecast::forecast;
+pub use compare::{compare, compare_page};
+pub use forecast::{forecast, forecast_sessions};
 pub use glimpse::glimpse;
 pub use handle_404::handle_404;
 pub use health_check::health_check;
-pub use realtime::realtime;
+pub use realtime::{realtime, realtime_history};
 pub use root::*;
+pub use spots::{nearest_spots, spots, spots_geojson};
 #[cfg(debug_assertions)]
 pub use watch::watch;
+pub use water_level::water_level;
@@ -1,13 +1,33 @@
 use super::AppError;
-use crate::{AppState, Realtime, SpotQuery};
+use crate::{AppState, HistoryQuery, Realtime, RealtimeHistory, SpotQuery, UnitsQuery};
 use axum::{extract::State, response::Json};
 use std::sync::Arc;
 
 pub async fn realtime(
     selected_spot: SpotQuery,
+    UnitsQuery(units): UnitsQuery,
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<Realtime>, AppError> {
     Ok(Json(
-        Realtime::try_get(Arc::new(selected_spot.0.into()), state.realtime_url).await?,
+        Realtime::try_get(
+            selected_spot.0,
+            state.realtime_url,
+            state.forecast_url,
+            &state.redis_pool,
+            units,
+        )
+        .await?,
+    ))
+}
+
+/// The spot's observations over the last `hours`, a day by default.
+pub async fn realtime_history(
+    selected_spot: SpotQuery,
+    HistoryQuery(hours): HistoryQuery,
+    UnitsQuery(units): UnitsQuery,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<RealtimeHistory>, AppError> {
+    Ok(Json(
+        RealtimeHistory::try_get(&selected_spot.0, hours, &state, units).await?,
     ))
 }
@@ -1,8 +1,11 @@
-use crate::{AppState, Forecast, Realtime, Spot, SpotParam, TEMPLATES, WaterQuality};
+use crate::{
+    AppState, Forecast, Realtime, Sessions, Spot, SpotParam, TEMPLATES, UnitsQuery, UnknownSpot,
+    WaterQuality,
+};
 use axum::{
     body::Body,
     extract::{Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
 use maud::{Markup, PreEscaped, html};
@@ -36,10 +39,20 @@ fn error_markup(error_type: &str, e: anyhow::Error) -> Markup {
     }
 }
 
+/// Hides the sessions when there aren't any to show.
+fn hide_sessions_markup() -> Markup {
+    html! {
+        script {
+            (PreEscaped("document.getElementById(\"session-container\").classList.add(\"hidden\")"))
+        }
+    }
+}
+
 /// Handler to return the website's index
 pub async fn root(
     State(state): State<Arc<AppState>>,
     selected_spot: Query<SpotParam>,
+    UnitsQuery(units): UnitsQuery,
 ) -> Result<Response, AppError> {
     // Create a channel to stream content to client as we get it.
     // Only allow one message at time so the buffer is cleared out
@@ -48,13 +61,14 @@ pub async fn root(
 
     let mut context = tera::Context::new();
 
-    let spot: Arc<Spot> = Arc::new(selected_spot.0.into());
+    let spot: Arc<Spot> = selected_spot.get_spot(&state.spots)?;
     // Wrap the sender in an arc so the channel doesn't close early
     let tx: Arc<Sender<Result<_, _>>> = Arc::new(tx);
 
     // Add the initial context to the page for the loading state
     context.insert("spot", &*spot);
     context.insert("breaks", &state.breaks);
+    context.insert("units", &units.labels());
     #[cfg(debug_assertions)]
     context.insert("live_reload", &true);
     #[cfg(not(debug_assertions))]
@@ -67,7 +81,7 @@ pub async fn root(
     let realtime_spot = spot.clone();
     let realtime_state = state.clone();
     tokio::spawn(async move {
-        match Realtime::try_get_string(realtime_spot, realtime_state).await {
+        match Realtime::try_get_string(realtime_spot, realtime_state, units).await {
             Ok(realtime) => {
                 let html = html!(
                     script type="application/json" id="realtime-data" {(
@@ -124,10 +138,27 @@ pub async fn root(
     });
 
     tokio::spawn(async move {
-        match Forecast::try_get_string(&spot, state).await {
+        match Forecast::try_get_string(&spot, state, units).await {
             Ok(forecast) => {
+                // Sessions are found in the forecast just fetched rather than
+                // fetching it again.
+                let sessions = match Sessions::try_find(&forecast, &spot.location, units)
+                    .and_then(|sessions| Ok(serde_json::to_string(&sessions)?))
+                {
+                    Ok(sessions) => html!(
+                        script type="application/json" id="sessions-data" {(
+                        PreEscaped(
+                                sessions)
+                        )}
+                    ),
+                    Err(e) => {
+                        error!("Failed to find the sessions: {e}");
+                        hide_sessions_markup()
+                    }
+                };
                 let html = html!
                     (
+                        (sessions)
                         script src="https://cdn.jsdelivr.net/npm/chart.js@4.4.3/dist/chart.umd.min.js" {}
                         script type="application/json" id="forecast-data" {(
                         PreEscaped(
@@ -143,6 +174,8 @@ pub async fn root(
             }
             Err(e) => {
                 error!("Failed to load the forecast data: {e}");
+                // The forecast shows its own error, just hide the sessions.
+                tx.send(Ok(hide_sessions_markup().into())).await.unwrap();
                 tx.send(Ok(html!((error_markup("forecast", e))).into()))
                     .await
                     .unwrap();
@@ -159,6 +192,7 @@ pub async fn root(
         .header("X-Content-Type-Options", "nosniff")
         .header("content-encoding", "none")
         .header("cache-control", "no-transform")
+        .header(header::SET_COOKIE, units.cookie())
         .body(body)?)
 }
 
@@ -168,6 +202,11 @@ pub struct AppError(pub anyhow::Error);
 // Tell axum how to convert `AppError` into a response.
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        // A bad spot in the url is the client's fault, not ours.
+        if let Some(e) = self.0.downcast_ref::<UnknownSpot>() {
+            return (StatusCode::BAD_REQUEST, e.to_string()).into_response();
+        }
+
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             format!("Something went wrong: {}", self.0),
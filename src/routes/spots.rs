@@ -0,0 +1,47 @@
+use crate::{
+    AppState, CoordinatesQuery, FeatureCollection, NearbySpot, SpotSummary,
+    truncate_to_two_decimals,
+};
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Json},
+};
+use std::sync::Arc;
+
+/// Returns every spot with its metadata and latest cached quality.
+pub async fn spots(State(state): State<Arc<AppState>>) -> Json<Vec<SpotSummary>> {
+    Json(SpotSummary::get_all(&state).await)
+}
+
+/// Returns every spot as a GeoJSON FeatureCollection of points.
+pub async fn spots_geojson(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let collection: FeatureCollection<SpotSummary> = SpotSummary::get_all(&state)
+        .await
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    (
+        [(header::CONTENT_TYPE, "application/geo+json")],
+        Json(collection),
+    )
+}
+
+/// Returns every spot ordered by its distance from the given coordinates.
+pub async fn nearest_spots(
+    CoordinatesQuery(coordinates): CoordinatesQuery,
+    State(state): State<Arc<AppState>>,
+) -> Json<Vec<NearbySpot>> {
+    Json(
+        state
+            .spots
+            .nearest(coordinates.lat, coordinates.lon)
+            .into_iter()
+            .map(|(spot, distance)| NearbySpot {
+                location: spot.location,
+                distance: truncate_to_two_decimals(distance),
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,16 @@
+use super::AppError;
+use crate::{AppState, SpotQuery, UnitsQuery, WaterLevel};
+use axum::{extract::State, http::header, response::IntoResponse};
+use std::sync::Arc;
+
+/// The lake level at the spot, `null` when it has no water level station.
+pub async fn water_level(
+    selected_spot: SpotQuery,
+    UnitsQuery(units): UnitsQuery,
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok((
+        [(header::CONTENT_TYPE, "application/json")],
+        WaterLevel::try_get_string(&selected_spot.0, &state, units).await?,
+    ))
+}
@@ -0,0 +1,273 @@
+use crate::{
+    AppState, Forecast, ForecastSummary, GOOD, Location, OK, ScoreSummary, Spot, UnitLabels, Units,
+    hour_label, is_daylight, quality::round_to_tenth, redis_utils,
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+use std::{ops::Range, sync::Arc};
+
+/// A stretch of daylight hours with good or fair to good conditions.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+pub struct Session {
+    pub start: String,
+    pub end: String,
+    /// Display friendly range, e.g. "Tue 06 AM to Tue 10 AM"
+    pub label: String,
+    pub hours: usize,
+    /// In the forecast's units
+    pub peak_wave_height: f64,
+    pub average_score: f64,
+}
+
+/// Every upcoming session at a spot over the forecast window.
+#[derive(serde::Serialize, Debug)]
+pub struct Sessions {
+    pub spot: &'static str,
+    /// The session with the highest average score, the earliest on a tie
+    pub best: Option<Session>,
+    pub sessions: Vec<Session>,
+    /// The units the wave heights are in
+    pub units: UnitLabels,
+}
+
+impl Sessions {
+    /// Checks for the sessions in the Redis cache, if not found finds them in
+    /// the forecast, cached or fetched, and updates the cache.
+    pub async fn try_get_string(
+        spot: &Spot,
+        state: Arc<AppState>,
+        units: Units,
+    ) -> anyhow::Result<String> {
+        let cache_key = format!("sessions-{}-{units}", spot.name);
+        if let Some(data) = redis_utils::get(&cache_key, &state.redis_pool).await {
+            tracing::info!("redis cache hit!");
+            return Ok(data);
+        }
+
+        let forecast = Forecast::try_get_string(spot, state.clone(), units).await?;
+        let data = Self::try_find(&forecast, &spot.location, units)?;
+        let data = serde_json::to_string(&data)?;
+
+        redis_utils::cache(&cache_key, &data, &state.redis_pool).await;
+
+        Ok(data)
+    }
+
+    /// Finds the contiguous daylight hours of the serialized forecast rated
+    /// good or fair to good, skipping any that are already over.
+    pub fn try_find(forecast: &str, location: &Location, units: Units) -> anyhow::Result<Self> {
+        let forecast = serde_json::from_str::<ForecastSummary>(forecast)?;
+        let starting_at = forecast.starting_at;
+
+        #[cfg(not(feature = "mock-time"))]
+        let now = Utc::now();
+        // When mocking time, the forecast starts now
+        #[cfg(feature = "mock-time")]
+        let now = starting_at;
+
+        let sessions = Self::find(
+            &forecast,
+            starting_at,
+            now,
+            (location.latitude, location.longitude),
+            location.time_zone,
+        );
+
+        let best = sessions
+            .iter()
+            .rev()
+            .max_by(|a, b| a.average_score.total_cmp(&b.average_score))
+            .cloned();
+
+        Ok(Self {
+            spot: location.slug,
+            best,
+            sessions,
+            units: units.labels(),
+        })
+    }
+
+    fn find(
+        forecast: &ForecastSummary,
+        starting_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+        coordinates: (f64, f64),
+        time_zone: Tz,
+    ) -> Vec<Session> {
+        let scores = forecast.scores.as_deref().unwrap_or_default();
+
+        let is_surfable = |hour: usize, score: &ScoreSummary| {
+            let time = starting_at + TimeDelta::hours(hour as i64);
+
+            [GOOD.0, OK.0].contains(&score.label.as_str())
+                && time + TimeDelta::hours(1) > now
+                && is_daylight(time, coordinates)
+        };
+
+        let mut sessions = Vec::new();
+        let mut start = None;
+        for (hour, score) in scores.iter().enumerate() {
+            if is_surfable(hour, score) {
+                start.get_or_insert(hour);
+            } else if let Some(start) = start.take() {
+                sessions.push(Session::new(forecast, starting_at, start..hour, time_zone));
+            }
+        }
+        if let Some(start) = start {
+            sessions.push(Session::new(
+                forecast,
+                starting_at,
+                start..scores.len(),
+                time_zone,
+            ));
+        }
+
+        sessions
+    }
+}
+
+impl Session {
+    fn new(
+        forecast: &ForecastSummary,
+        starting_at: DateTime<Utc>,
+        hours: Range<usize>,
+        time_zone: Tz,
+    ) -> Self {
+        let scores = &forecast.scores.as_deref().unwrap_or_default()[hours.clone()];
+
+        let peak_wave_height = forecast.wave_height[hours.clone()]
+            .iter()
+            .copied()
+            .fold(0.0, f64::max);
+        let average_score =
+            scores.iter().map(|score| score.value).sum::<f64>() / scores.len() as f64;
+
+        let start = starting_at + TimeDelta::hours(hours.start as i64);
+        let end = starting_at + TimeDelta::hours(hours.end as i64);
+
+        Self {
+            start: start.to_rfc3339(),
+            end: end.to_rfc3339(),
+            label: format!(
+                "{} to {}",
+                hour_label(start, time_zone),
+                hour_label(end, time_zone)
+            ),
+            hours: hours.len(),
+            peak_wave_height,
+            average_score: round_to_tenth(average_score),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HourlyForecast, POOR, Quality, Score, ScoreBreakdown};
+    use chrono::DurationRound;
+    use chrono_tz::US::Central;
+
+    const MILWAUKEE: (f64, f64) = (43.0389, -87.9065);
+    // 10 AM CDT
+    const STARTING_AT: &str = "2024-06-10T15:00:00+00:00";
+
+    fn score(quality: &'static Quality, value: f64) -> Score {
+        Score {
+            value,
+            breakdown: ScoreBreakdown {
+                size: 0.0,
+                wind_speed: 0.0,
+                wind_angle: 0.0,
+                period: 0.0,
+            },
+            label: quality.0,
+            color: quality.1,
+        }
+    }
+
+    /// A forecast of each hour's quality, wave height and score, as it's cached.
+    /// The scores are set rather than rated from the conditions.
+    fn forecast(hours: &[(&'static Quality, f64, f64)]) -> ForecastSummary {
+        // The current wave data is relative to now
+        let now = Utc::now().duration_trunc(TimeDelta::hours(1)).unwrap();
+        // Smoothing the wave heights over three hours drops the last two
+        let series = || vec![0.0; hours.len() + 2];
+        let optional = || vec![Some(0.0); hours.len() + 2];
+        let hourly = HourlyForecast {
+            updated_at: Some(now),
+            starting_at: now,
+            wave_height: series(),
+            wave_period: optional(),
+            wave_direction: optional(),
+            wind_speed: series(),
+            wind_gust: optional(),
+            wind_direction: series(),
+            temperature: optional(),
+            dewpoint: optional(),
+            probability_of_precipitation: optional(),
+            probability_of_thunder: optional(),
+            cloud_cover: optional(),
+        };
+
+        let mut forecast = Forecast::try_from_hourly(&hourly, Units::IMPERIAL, Central).unwrap();
+        forecast.wave_height = hours.iter().map(|(_, height, _)| *height).collect();
+        forecast.scores = Some(
+            hours
+                .iter()
+                .map(|(quality, _, value)| score(quality, *value))
+                .collect(),
+        );
+
+        serde_json::from_str(&serde_json::to_string(&forecast).unwrap()).unwrap()
+    }
+
+    fn starting_at() -> DateTime<Utc> {
+        STARTING_AT.parse().unwrap()
+    }
+
+    #[test]
+    fn finds_contiguous_good_and_fair_hours() {
+        let forecast = forecast(&[
+            (&POOR, 1.0, 2.0),
+            (&OK, 2.0, 4.0),
+            (&GOOD, 3.5, 7.0),
+            (&OK, 2.5, 5.0),
+            (&POOR, 1.0, 2.0),
+            (&GOOD, 2.0, 6.0),
+        ]);
+
+        let sessions = Sessions::find(&forecast, starting_at(), starting_at(), MILWAUKEE, Central);
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].start, "2024-06-10T16:00:00+00:00");
+        assert_eq!(sessions[0].end, "2024-06-10T19:00:00+00:00");
+        assert_eq!(sessions[0].label, "Mon 11 AM to Mon 02 PM");
+        assert_eq!(sessions[0].hours, 3);
+        assert_eq!(sessions[0].peak_wave_height, 3.5);
+        assert_eq!(sessions[0].average_score, 5.3);
+        assert_eq!(sessions[1].hours, 1);
+    }
+
+    #[test]
+    fn a_session_ends_at_dark() {
+        // 10 AM through 10 PM CDT, sunset is 8:28 PM
+        let forecast = forecast(&[(&GOOD, 2.0, 6.0); 13]);
+
+        let sessions = Sessions::find(&forecast, starting_at(), starting_at(), MILWAUKEE, Central);
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].label, "Mon 10 AM to Mon 09 PM");
+    }
+
+    #[test]
+    fn sessions_already_over_are_skipped() {
+        let forecast = forecast(&[(&GOOD, 2.0, 6.0), (&POOR, 1.0, 2.0), (&OK, 2.0, 4.0)]);
+        let now = starting_at() + TimeDelta::hours(2);
+
+        let sessions = Sessions::find(&forecast, starting_at(), now, MILWAUKEE, Central);
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].start, "2024-06-10T17:00:00+00:00");
+    }
+}
@@ -1,66 +1,285 @@
-use super::Quality;
+use super::{Conditions, DirectionWindow, QualityProfile, Rating, Score};
+use crate::{
+    AppState, CachedQuality, Gridpoint, Realtime,
+    configuration::SpotSettings,
+    geojson::{Feature, Geometry},
+    great_circle_distance,
+    gridpoint::{cache_gridpoint, cached_gridpoint},
+};
 use axum::{
-    extract::{FromRequestParts, Query},
+    extract::{FromRequestParts, Query, rejection::QueryRejection},
     http::{StatusCode, request::Parts},
     response::{IntoResponse, Json, Response},
 };
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
+use chrono_tz::Tz;
 use serde_json::json;
-use std::sync::OnceLock;
+use std::{fmt, sync::Arc};
+use tokio::sync::OnceCell;
 
 #[derive(serde::Deserialize, Debug)]
 pub struct SpotParam {
-    pub spot: Option<Location>,
+    pub spot: Option<String>,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
 }
 
 impl SpotParam {
-    pub fn get_spot(&mut self) -> Location {
-        // Get the selected spot, fallback to Atwater
-        self.spot.take().unwrap_or(Location::Atwater)
+    /// Gets the selected spot. Without one, picks the spot nearest to the given
+    /// coordinates, then falls back to the default spot.
+    ///
+    /// # Errors
+    /// Returns [`UnknownSpot`] if the requested spot isn't in the registry.
+    pub fn get_spot(&self, spots: &Spots) -> Result<Arc<Spot>, UnknownSpot> {
+        match (&self.spot, self.lat, self.lon) {
+            (Some(spot), _, _) => spots.get(spot).ok_or_else(|| UnknownSpot {
+                requested: spot.clone(),
+                valid: spots.slugs(),
+            }),
+            (None, Some(lat), Some(lon)) => Ok(spots.nearest(lat, lon)[0].0.clone()),
+            _ => Ok(spots.default_spot()),
+        }
     }
 }
 
-/// Custom query extractor for api endpoints
-pub struct SpotQuery(pub SpotParam);
+/// Query for the nearest spots api
+#[derive(serde::Deserialize, Debug)]
+pub struct CoordinatesParam {
+    pub lat: f64,
+    pub lon: f64,
+}
 
-// Ensure api requests with bad query params return JSON rather than plain text.
-impl<S> FromRequestParts<S> for SpotQuery
+/// Custom coordinates extractor for api endpoints
+pub struct CoordinatesQuery(pub CoordinatesParam);
+
+impl<S> FromRequestParts<S> for CoordinatesQuery
 where
     S: Send + Sync,
 {
     type Rejection = Response;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let param = match Query::<CoordinatesParam>::from_request_parts(parts, state).await {
+            Ok(Query(param)) => param,
+            Err(e) => return Err(query_rejection(e)),
+        };
+
+        if !(-90.0..=90.0).contains(&param.lat) || !(-180.0..=180.0).contains(&param.lon) {
+            let error_response = (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Invalid query parameter",
+                    "details": "lat must be within ±90 and lon within ±180"
+                })),
+            );
+            return Err(error_response.into_response());
+        }
+
+        Ok(CoordinatesQuery(param))
+    }
+}
+
+/// Turns a query string parsing error into a JSON response.
+pub(crate) fn query_rejection(e: QueryRejection) -> Response {
+    tracing::error!("Query parse error: {:?}", e);
+    let error_response = (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "Invalid query parameter",
+            "details": e.to_string()
+        })),
+    );
+    error_response.into_response()
+}
+
+/// Error for a requested spot that isn't configured.
+#[derive(Debug)]
+pub struct UnknownSpot {
+    pub requested: String,
+    pub valid: Vec<&'static str>,
+}
+
+impl fmt::Display for UnknownSpot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown spot '{}', expected one of: {}",
+            self.requested,
+            self.valid.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownSpot {}
+
+/// Custom query extractor for api endpoints
+pub struct SpotQuery(pub Arc<Spot>);
+
+// Ensure api requests with bad query params return JSON rather than plain text.
+impl FromRequestParts<Arc<AppState>> for SpotQuery {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
         // Parse query string manually to catch errors
-        match Query::<SpotParam>::from_request_parts(parts, _state).await {
-            Ok(Query(param)) => Ok(SpotQuery(param)),
-            Err(e) => {
-                tracing::error!("Query parse error: {:?}", e);
-                let error_response = (
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "error": "Invalid query parameter",
-                        "details": e.to_string()
-                    })),
-                );
-                Err(error_response.into_response())
-            }
+        let param = match Query::<SpotParam>::from_request_parts(parts, state).await {
+            Ok(Query(param)) => param,
+            Err(e) => return Err(query_rejection(e)),
+        };
+
+        match param.get_spot(&state.spots) {
+            Ok(spot) => Ok(SpotQuery(spot)),
+            Err(e) => Err(unknown_spot_rejection(e)),
+        }
+    }
+}
+
+/// Query for comparing several spots, e.g. `?spots=atwater,racine`
+#[derive(serde::Deserialize, Debug)]
+pub struct SpotsParam {
+    pub spots: Option<String>,
+}
+
+impl SpotsParam {
+    /// Gets the selected spots by name or slug, every spot when none are given.
+    ///
+    /// # Errors
+    /// Returns [`UnknownSpot`] for the first requested spot not in the registry.
+    pub fn get_spots(&self, spots: &Spots) -> Result<Vec<Arc<Spot>>, UnknownSpot> {
+        let Some(requested) = &self.spots else {
+            return Ok(spots.iter().cloned().collect());
+        };
+
+        requested
+            .split(',')
+            .map(str::trim)
+            .filter(|spot| !spot.is_empty())
+            .map(|spot| {
+                spots.get(spot).ok_or_else(|| UnknownSpot {
+                    requested: spot.to_string(),
+                    valid: spots.slugs(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Custom query extractor for api endpoints taking several spots
+pub struct SpotsQuery(pub Vec<Arc<Spot>>);
+
+impl FromRequestParts<Arc<AppState>> for SpotsQuery {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let param = match Query::<SpotsParam>::from_request_parts(parts, state).await {
+            Ok(Query(param)) => param,
+            Err(e) => return Err(query_rejection(e)),
+        };
+
+        match param.get_spots(&state.spots) {
+            Ok(spots) => Ok(SpotsQuery(spots)),
+            Err(e) => Err(unknown_spot_rejection(e)),
         }
     }
 }
 
+/// Turns an unknown spot into a JSON response listing the valid spots.
+fn unknown_spot_rejection(e: UnknownSpot) -> Response {
+    tracing::error!("Unknown spot requested: {}", e.requested);
+    let error_response = (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "Invalid query parameter",
+            "details": e.to_string(),
+            "valid_spots": e.valid,
+        })),
+    );
+    error_response.into_response()
+}
+
 #[derive(serde::Serialize, Debug)]
 pub struct Spot {
-    pub forecast_path: &'static str,
-    pub realtime_path: &'static str,
-    pub quality_query: &'static str,
-    pub status_query: &'static str,
-    pub fallback_realtime_path: Option<&'static str>,
+    /// Resolved lazily from the spot's coordinates unless configured.
+    #[serde(skip)]
+    forecast_path: OnceCell<String>,
+    pub quality_query: String,
+    pub status_query: String,
     pub location: Location,
     pub live_feed_url: Option<&'static str>,
     pub name: &'static str,
     pub has_bouy: bool,
+    /// NDBC stations realtime data is merged from, in order of preference
+    pub stations: Vec<&'static str>,
+    /// Minutes after which the latest observation is stale
+    pub max_age_minutes: i64,
+    /// NWS nearshore marine zone
+    pub marine_zone: Option<&'static str>,
+    /// NWS public zone beach hazards are issued for
+    pub beach_zone: Option<&'static str>,
+    /// NOAA CO-OPS station the lake level is measured at
+    pub water_level_station: Option<&'static str>,
+    /// ERDDAP dataset of the lake's GLCFS wave model output
+    pub glcfs_dataset: Option<&'static str>,
 }
 
+fn get_forecast_path(gridpoint: &str) -> String {
+    format!("/gridpoints/{gridpoint}")
+}
+
+impl Spot {
+    /// Gets the path to the spot's NWS gridpoint. The first time it's needed
+    /// it's read from Redis, or resolved from the spot's coordinates and cached.
+    ///
+    /// # Errors
+    /// Errors if the NWS points api can't resolve the coordinates.
+    pub async fn try_forecast_path(
+        &self,
+        forecast_url: &str,
+        redis_pool: &Pool<RedisConnectionManager>,
+    ) -> anyhow::Result<&str> {
+        self.forecast_path
+            .get_or_try_init(|| async {
+                if let Some(path) = cached_gridpoint(self, redis_pool).await {
+                    return anyhow::Ok(path);
+                }
+
+                let path = Gridpoint::try_resolve(
+                    self.location.latitude,
+                    self.location.longitude,
+                    forecast_url,
+                )
+                .await?
+                .path();
+                cache_gridpoint(self, &path, redis_pool).await;
+
+                anyhow::Ok(path)
+            })
+            .await
+            .map(String::as_str)
+            .map_err(|e| {
+                anyhow::anyhow!("Unable to resolve the NWS gridpoint for {}: {e}", self.name)
+            })
+    }
+
+    /// Sets the gridpoint path if it hasn't already been resolved.
+    pub fn set_forecast_path(&self, path: String) {
+        let _ = self.forecast_path.set(path);
+    }
+
+    pub fn has_forecast_path(&self) -> bool {
+        self.forecast_path.initialized()
+    }
+}
+
+/// Observations older than a day are stale unless the spot says otherwise.
+const DEFAULT_MAX_AGE_MINUTES: i64 = 60 * 24;
+
 fn get_status_query(id: &str) -> String {
     format!("?f=json&objectIds={id}&outFields=MAP_STATUS")
 }
@@ -71,183 +290,217 @@ fn get_quality_query(id: &str) -> String {
     )
 }
 
-macro_rules! quality_once_lock {
-    ($id:expr) => {{
-        // Identities created in Rust macros are hygienic, attaching invisible syntax context to
-        // all indentifiers so repeated static QUALITYs never collide
-        static QUALITY: OnceLock<String> = OnceLock::new();
-
-        QUALITY.get_or_init(|| get_quality_query($id))
-    }};
+impl From<&'static SpotSettings> for Spot {
+    fn from(settings: &'static SpotSettings) -> Self {
+        Spot {
+            forecast_path: OnceCell::new_with(settings.gridpoint.as_deref().map(get_forecast_path)),
+            quality_query: get_quality_query(&settings.beach_id),
+            status_query: get_status_query(&settings.beach_id),
+            location: settings.into(),
+            live_feed_url: settings.live_feed_url.as_deref(),
+            name: &settings.name,
+            has_bouy: settings.has_bouy,
+            stations: settings.stations.iter().map(String::as_str).collect(),
+            max_age_minutes: settings.max_age_minutes.unwrap_or(DEFAULT_MAX_AGE_MINUTES),
+            marine_zone: settings.marine_zone.as_deref(),
+            beach_zone: settings.beach_zone.as_deref(),
+            water_level_station: settings.water_level_station.as_deref(),
+            glcfs_dataset: settings.glcfs_dataset.as_deref(),
+        }
+    }
 }
 
-macro_rules! status_once_lock {
-    ($id:expr) => {{
-        static STATUS: OnceLock<String> = OnceLock::new();
+// -- Water Quality Queries --
+// Base path for all spot queries
+pub const QUALITY_PATH: &str = "/arcgis2/rest/services/OGW_Beach_Monitoring/OGW_Beach_Monitoring_Locations_Ext/MapServer/0/query";
+// -- --
 
-        STATUS.get_or_init(|| get_status_query($id))
-    }};
-}
+/// Every spot in `config/spots.yml`, built once at startup.
+#[derive(Clone, Debug)]
+pub struct Spots(Vec<Arc<Spot>>);
 
-impl From<SpotParam> for Spot {
-    fn from(mut val: SpotParam) -> Self {
-        match val.get_spot() {
-            Location::Bradford => Spot {
-                forecast_path: BRADFORD_PATH,
-                realtime_path: BRADFORD_REALTIME_PATH,
-                quality_query: quality_once_lock!(BRADFORD_QUALITY_ID),
-                status_query: status_once_lock!(BRADFORD_QUALITY_ID),
-                fallback_realtime_path: None,
-                location: Location::Bradford,
-                live_feed_url: None,
-                name: "Bradford",
-                has_bouy: false,
-            },
-            Location::PortWashington => Spot {
-                forecast_path: PORT_WASHINGTON_PATH,
-                realtime_path: PORT_WASHINGTON_REALTIME_PATH,
-                quality_query: quality_once_lock!(PORT_WASHINGTON_QUALITY_ID),
-                status_query: status_once_lock!(PORT_WASHINGTON_QUALITY_ID),
-                fallback_realtime_path: None,
-                location: Location::PortWashington,
-                live_feed_url: None,
-                name: "Port Washington",
-                has_bouy: false,
-            },
-            Location::Sheboygan => Spot {
-                forecast_path: SHEBOYGAN_PATH,
-                realtime_path: SHEBOYGAN_REALTIME_PATH,
-                quality_query: quality_once_lock!(SHEBOYGAN_NORTH_QUALITY_ID),
-                status_query: status_once_lock!(SHEBOYGAN_NORTH_QUALITY_ID),
-                fallback_realtime_path: Some(SHEBOYGAN_FALLBACK_REALTIME_PATH),
-                location: Location::Sheboygan,
-                live_feed_url: Some(
-                    "https://www.youtube.com/embed/13j5iZkMpbE?si=rJuOhwtAzm6u6Oru",
-                ),
-                name: "Sheboygan - North",
-                has_bouy: true,
-            },
-            Location::SheboyganSouth => Spot {
-                forecast_path: SHEBOYGAN_SOUTH_PATH,
-                realtime_path: SHEBOYGAN_REALTIME_PATH,
-                quality_query: quality_once_lock!(SHEBOYGAN_SOUTH_QUALITY_ID),
-                status_query: status_once_lock!(SHEBOYGAN_SOUTH_QUALITY_ID),
-                fallback_realtime_path: Some(SHEBOYGAN_FALLBACK_REALTIME_PATH),
-                location: Location::SheboyganSouth,
-                live_feed_url: Some(
-                    "https://www.youtube.com/embed/ABRrwDe5Hho?si=yCi2OVy3RIbY_5kC&amp;controls=0",
-                ),
-                name: "Sheboygan - South",
-                has_bouy: true,
-            },
-            Location::Racine => Spot {
-                forecast_path: RACINE_PATH,
-                realtime_path: RACINE_REALTIME_PATH,
-                quality_query: quality_once_lock!(RACINE_QUALITY_ID),
-                status_query: status_once_lock!(RACINE_QUALITY_ID),
-                fallback_realtime_path: Some(RACINE_FALLBACK_REALTIME_PATH),
-                location: Location::Racine,
-                live_feed_url: None,
-                name: "Racine",
-                has_bouy: true,
-            },
-            Location::Atwater => Spot {
-                forecast_path: ATWATER_PATH,
-                realtime_path: ATWATER_REALTIME_PATH,
-                quality_query: quality_once_lock!(ATWATER_QUALITY_ID),
-                status_query: status_once_lock!(ATWATER_QUALITY_ID),
-                fallback_realtime_path: Some(BRADFORD_REALTIME_PATH),
-                location: Location::Atwater,
-                live_feed_url: None,
-                name: "Atwater",
-                has_bouy: true,
-            },
+impl Spots {
+    /// Builds the registry from the configured spots.
+    ///
+    /// # Errors
+    /// Errors if no spots are configured or a slug is used more than once.
+    pub fn try_new(settings: &'static [SpotSettings]) -> anyhow::Result<Self> {
+        if settings.is_empty() {
+            anyhow::bail!("no spots configured");
+        }
+
+        let mut spots: Vec<Arc<Spot>> = Vec::with_capacity(settings.len());
+        for spot in settings {
+            if spots.iter().any(|s| s.location.slug == spot.slug) {
+                anyhow::bail!("spot slug '{}' is configured more than once", spot.slug);
+            }
+            spots.push(Arc::new(spot.into()));
         }
+
+        Ok(Self(spots))
     }
-}
 
-// -- Forecast Paths --
-pub const ATWATER_PATH: &str = "/gridpoints/MKX/90,67";
-const BRADFORD_PATH: &str = "/gridpoints/MKX/90,66";
-const SHEBOYGAN_PATH: &str = "/gridpoints/MKX/94,99";
-const SHEBOYGAN_SOUTH_PATH: &str = "/gridpoints/MKX/94,98";
-const PORT_WASHINGTON_PATH: &str = "/gridpoints/MKX/91,80";
-const RACINE_PATH: &str = "/gridpoints/MKX/94,52";
-// -- --
+    /// Finds a spot by its slug, or by its display name to keep older links working.
+    pub fn get(&self, spot: &str) -> Option<Arc<Spot>> {
+        self.0
+            .iter()
+            .find(|s| s.location.slug == spot || s.name == spot)
+            .cloned()
+    }
 
-// -- Realtime Paths --
-//
-// -- Bouy Paths --
-pub const ATWATER_REALTIME_PATH: &str = "/data/realtime2/45013.txt";
-const SHEBOYGAN_REALTIME_PATH: &str = "/data/realtime2/45218.txt";
-const RACINE_REALTIME_PATH: &str = "/data/realtime2/45199.txt";
-// -- --
+    /// The first configured spot.
+    pub fn default_spot(&self) -> Arc<Spot> {
+        self.0[0].clone()
+    }
 
-// -- Land Based Weather Station Paths --
-const BRADFORD_REALTIME_PATH: &str = "/data/realtime2/MLWW3.txt";
-const PORT_WASHINGTON_REALTIME_PATH: &str = "/data/realtime2/PWAW3.txt";
+    /// Every spot paired with its distance in miles from the given coordinates,
+    /// closest first.
+    pub fn nearest(&self, lat: f64, lon: f64) -> Vec<(Arc<Spot>, f64)> {
+        let mut spots = self
+            .0
+            .iter()
+            .map(|spot| {
+                let distance = great_circle_distance(
+                    (lat, lon),
+                    (spot.location.latitude, spot.location.longitude),
+                );
+                (spot.clone(), distance)
+            })
+            .collect::<Vec<_>>();
 
-const SHEBOYGAN_FALLBACK_REALTIME_PATH: &str = "/data/realtime2/SGNW3.txt";
-const RACINE_FALLBACK_REALTIME_PATH: &str = "/data/realtime2/KNSW3.txt";
-// -- --
-//
-// -- Water Quality Queries --
-// Base path for all spot queries
-pub const QUALITY_PATH: &str = "/arcgis2/rest/services/OGW_Beach_Monitoring/OGW_Beach_Monitoring_Locations_Ext/MapServer/0/query";
-//
-const ATWATER_QUALITY_ID: &str = "171";
-const RACINE_QUALITY_ID: &str = "204";
-const BRADFORD_QUALITY_ID: &str = "192";
-const SHEBOYGAN_NORTH_QUALITY_ID: &str = "170";
-const SHEBOYGAN_SOUTH_QUALITY_ID: &str = "382";
-const PORT_WASHINGTON_QUALITY_ID: &str = "100";
-// -- --
-//
-// -- --
+        spots.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        spots
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<Spot>> {
+        self.0.iter()
+    }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
-pub enum Location {
-    Atwater,
-    Bradford,
-    #[serde(rename = "Sheboygan - North")]
-    Sheboygan,
-    #[serde(rename = "Sheboygan - South")]
-    SheboyganSouth,
-    #[serde(rename = "Port Washington")]
-    PortWashington,
-    Racine,
+    pub fn get_all(&self) -> Vec<Location> {
+        self.0.iter().map(|spot| spot.location).collect()
+    }
+
+    pub fn slugs(&self) -> Vec<&'static str> {
+        self.0.iter().map(|spot| spot.location.slug).collect()
+    }
+}
+
+#[derive(serde::Serialize, Debug, Clone, Copy)]
+pub struct Location {
+    pub name: &'static str,
+    pub slug: &'static str,
+    pub region: &'static str,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Compass bearing, in degrees, the beach faces
+    pub bearing: f64,
+    pub swell_window: DirectionWindow,
+    /// Time zone times at the spot are shown in
+    #[serde(skip)]
+    pub time_zone: Tz,
+    #[serde(skip)]
+    pub quality_profile: &'static QualityProfile,
+}
+
+impl From<&'static SpotSettings> for Location {
+    fn from(settings: &'static SpotSettings) -> Self {
+        Self {
+            name: &settings.name,
+            slug: &settings.slug,
+            region: &settings.region,
+            latitude: settings.latitude,
+            longitude: settings.longitude,
+            bearing: settings.bearing,
+            swell_window: settings
+                .swell_window
+                .unwrap_or_else(|| DirectionWindow::facing(settings.bearing)),
+            time_zone: settings.time_zone,
+            quality_profile: &settings.quality_profile,
+        }
+    }
+}
+
+/// A spot and how far away it is, in miles.
+#[derive(serde::Serialize, Debug)]
+pub struct NearbySpot {
+    #[serde(flatten)]
+    pub location: Location,
+    pub distance: f64,
 }
 
 impl Location {
-    pub fn get_all() -> Vec<Location> {
-        Self::into_iter().collect()
+    pub fn get_quality(&self, conditions: &Conditions) -> Rating {
+        self.quality_profile
+            .get_quality(
+                conditions.wave_height,
+                conditions.wind_speed,
+                conditions.wind_direction,
+            )
+            .with_swell(
+                conditions.wave_period,
+                conditions.wave_direction,
+                &self.swell_window,
+            )
+    }
+
+    /// Scores the conditions at the spot along with its categorical quality.
+    pub fn get_score(&self, conditions: &Conditions, rating: &Rating) -> Score {
+        Score::new(conditions, self.bearing, rating.quality)
     }
+}
+
+/// A spot's metadata along with its latest cached quality.
+#[derive(serde::Serialize, Debug)]
+pub struct SpotSummary {
+    #[serde(flatten)]
+    pub location: Location,
+    pub bouy_id: Option<&'static str>,
+    pub station_id: Option<&'static str>,
+    pub has_bouy: bool,
+    pub live_feed_url: Option<&'static str>,
+    pub quality: Option<CachedQuality>,
+}
 
-    fn into_iter() -> core::array::IntoIter<Self, 6> {
-        [
-            Self::Atwater,
-            Self::Bradford,
-            Self::Sheboygan,
-            Self::SheboyganSouth,
-            Self::PortWashington,
-            Self::Racine,
-        ]
-        .into_iter()
+impl SpotSummary {
+    /// Summarizes every spot. Only already cached realtime data is used for
+    /// the quality so this never waits on NOAA.
+    pub async fn get_all(state: &AppState) -> Vec<Self> {
+        let spots = state
+            .spots
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<&Spot>>();
+        let qualities = Realtime::get_cached_qualities(&spots, state).await;
+
+        spots
+            .into_iter()
+            .zip(qualities)
+            .map(|(spot, quality)| {
+                let mut stations = spot.stations.iter().copied();
+                let bouy_id = if spot.has_bouy { stations.next() } else { None };
+                let station_id = stations.next();
+
+                Self {
+                    location: spot.location,
+                    bouy_id,
+                    station_id,
+                    has_bouy: spot.has_bouy,
+                    live_feed_url: spot.live_feed_url,
+                    quality,
+                }
+            })
+            .collect()
     }
+}
 
-    pub fn get_quality(
-        &self,
-        wave_height: f64,
-        wind_speed: f64,
-        wind_direction: f64,
-    ) -> &'static Quality {
-        match self {
-            Self::Atwater | Self::Bradford | Self::Sheboygan => {
-                Quality::south(wave_height, wind_speed, wind_direction)
-            }
-            Self::PortWashington | Self::Racine | Self::SheboyganSouth => {
-                Quality::north(wave_height, wind_speed, wind_direction)
-            }
+impl From<SpotSummary> for Feature<SpotSummary> {
+    fn from(summary: SpotSummary) -> Self {
+        Self {
+            geometry: Geometry::Point {
+                coordinates: [summary.location.longitude, summary.location.latitude],
+            },
+            properties: summary,
         }
     }
 }
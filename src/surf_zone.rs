@@ -0,0 +1,185 @@
+use crate::{RipCurrentRisk, Spot, marine::try_get_json};
+
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
+use chrono::{DateTime, Utc};
+
+/// Segments of a product are separated by a line of `$$`.
+const SEGMENT_END: &str = "$$";
+const RIP_CURRENT_RISK: &str = "Rip Current Risk";
+
+#[derive(serde::Deserialize)]
+struct ProductsResponse {
+    #[serde(rename = "@graph")]
+    graph: Vec<ProductSummary>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProductSummary {
+    id: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProductResponse {
+    /// RFC 3339, e.g. "2025-05-23T14:02:00+00:00"
+    issuance_time: String,
+    product_text: String,
+}
+
+/// The latest NWS Surf Zone Forecast (SRF) of the office covering a spot.
+#[derive(Debug)]
+pub struct SurfZoneForecast {
+    pub issued: DateTime<Utc>,
+    pub text: String,
+}
+
+impl SurfZoneForecast {
+    /// Gets the latest surf zone forecast of the spot's NWS office, `None` when
+    /// the office hasn't issued one, which most do only during swim season.
+    ///
+    /// # Errors
+    /// When the spot's gridpoint can't be resolved or the NWS can't be reached.
+    pub async fn try_get(
+        spot: &Spot,
+        forecast_url: &str,
+        redis_pool: &Pool<RedisConnectionManager>,
+    ) -> anyhow::Result<Option<Self>> {
+        // e.g. "/gridpoints/MKX/90,67"
+        let forecast_path = spot.try_forecast_path(forecast_url, redis_pool).await?;
+        let Some(office) = forecast_path.split('/').nth(2) else {
+            anyhow::bail!("No NWS office in the gridpoint path {forecast_path}");
+        };
+
+        let products = try_get_json::<ProductsResponse>(&format!(
+            "{forecast_url}/products/types/SRF/locations/{office}"
+        ))
+        .await?;
+
+        let Some(latest) = products.graph.first() else {
+            return Ok(None);
+        };
+
+        let product =
+            try_get_json::<ProductResponse>(&format!("{forecast_url}/products/{}", latest.id))
+                .await?;
+
+        Ok(Some(Self {
+            issued: DateTime::parse_from_rfc3339(&product.issuance_time)?.with_timezone(&Utc),
+            text: product.product_text,
+        }))
+    }
+
+    /// Finds the first rip current risk in the segment covering the zone, which
+    /// is the risk for the rest of today.
+    pub fn rip_current_risk(&self, zone: &str) -> Option<RipCurrentRisk> {
+        self.text
+            .split(SEGMENT_END)
+            .find(|segment| parse_zones(segment).iter().any(|z| z == zone))?
+            .lines()
+            .find_map(|line| {
+                let value = line.trim().strip_prefix(RIP_CURRENT_RISK)?;
+                // e.g. "*...........Moderate."
+                match value
+                    .trim_start_matches(['*', '.'])
+                    .trim_end_matches('.')
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "low" => Some(RipCurrentRisk::Low),
+                    "moderate" => Some(RipCurrentRisk::Moderate),
+                    "high" => Some(RipCurrentRisk::High),
+                    _ => None,
+                }
+            })
+    }
+}
+
+/// Parses the zones a segment is for from its UGC line, e.g.
+/// `WIZ052-060>062-240300-` is WIZ052, WIZ060, WIZ061 and WIZ062 until the
+/// 24th at 03:00. Zones of the same state and type only list their number, and
+/// the line can wrap.
+fn parse_zones(segment: &str) -> Vec<String> {
+    let mut zones = vec![];
+    let mut prefix = "";
+
+    let codes = segment
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| !is_ugc_line(line))
+        .take_while(|line| is_ugc_line(line))
+        .flat_map(|line| line.split('-'))
+        .filter(|code| !code.is_empty());
+
+    for code in codes {
+        // The expiration time ends the zones
+        if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+            break;
+        }
+
+        let numbers = match code.get(..3) {
+            Some(state) if state.chars().all(|c| c.is_ascii_alphabetic()) => {
+                prefix = state;
+                &code[3..]
+            }
+            _ => code,
+        };
+
+        match numbers.split_once('>') {
+            Some((from, to)) => {
+                let (Ok(from), Ok(to)) = (from.parse::<u16>(), to.parse::<u16>()) else {
+                    continue;
+                };
+                zones.extend((from..=to).map(|number| format!("{prefix}{number:03}")));
+            }
+            None => zones.push(format!("{prefix}{numbers}")),
+        }
+    }
+
+    zones
+}
+
+/// Whether the line is made up of UGC codes, e.g. `WIZ052-060-` or `066-240300-`.
+fn is_ugc_line(line: &str) -> bool {
+    line.ends_with('-')
+        && line
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-' || c == '>')
+        && line.chars().any(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forecast() -> SurfZoneForecast {
+        SurfZoneForecast {
+            issued: "2025-05-23T14:02:00Z".parse().unwrap(),
+            text: include_str!("../tests/fixtures/nws/SRFMKX.txt").to_string(),
+        }
+    }
+
+    #[test]
+    fn the_risk_is_from_the_zones_segment() {
+        let forecast = forecast();
+
+        assert_eq!(
+            forecast.rip_current_risk("WIZ052"),
+            Some(RipCurrentRisk::Low)
+        );
+        assert_eq!(
+            forecast.rip_current_risk("WIZ066"),
+            Some(RipCurrentRisk::Moderate)
+        );
+        assert_eq!(forecast.rip_current_risk("WIZ034"), None);
+    }
+
+    #[test]
+    fn zones_expand_ranges_and_wrapped_lines() {
+        assert_eq!(
+            parse_zones("\nWIZ052-060>062-\nMIZ037-240300-\nSheboygan-\n"),
+            ["WIZ052", "WIZ060", "WIZ061", "WIZ062", "MIZ037"]
+        );
+    }
+}
@@ -0,0 +1,637 @@
+use std::{fmt, str::FromStr};
+
+use crate::spot::query_rejection;
+
+use anyhow::bail;
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{header, request::Parts},
+    response::Response,
+};
+
+/// Metres in a foot, exactly.
+const METERS_PER_FOOT: f64 = 0.3048;
+/// Metres in a statute mile, exactly.
+const METERS_PER_MILE: f64 = 1609.344;
+/// Metres in a nautical mile, exactly.
+const METERS_PER_NAUTICAL_MILE: f64 = 1852.0;
+const SECONDS_PER_HOUR: f64 = 3600.0;
+/// Name of the cookie remembering the units picked on the pages.
+const UNITS_COOKIE: &str = "units";
+/// How long the units cookie lasts, a year.
+const UNITS_COOKIE_MAX_AGE: u32 = 365 * 24 * 60 * 60;
+
+/// A unit of measure as declared by a data source, e.g. the `uom` of an NWS
+/// gridpoint layer or the units row of an NDBC file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    Meter,
+    Foot,
+    Second,
+    MeterPerSecond,
+    KilometerPerHour,
+    Knot,
+    MilePerHour,
+    Celsius,
+    Fahrenheit,
+    Degree,
+    Percent,
+}
+
+/// What a unit measures. Each kind has a canonical unit values are kept in:
+/// metres, seconds, metres per second, degrees Celsius, degrees and percent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Length,
+    Duration,
+    Speed,
+    Temperature,
+    Angle,
+    Percent,
+}
+
+impl FromStr for Unit {
+    type Err = anyhow::Error;
+
+    /// Parses the spellings of the NWS (e.g. "wmoUnit:km_h-1"), NDBC (e.g.
+    /// "m/s") and ERDDAP (e.g. "degrees").
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        // NWS codes are namespaced, e.g. "wmoUnit:m" or "nwsUnit:s"
+        let code = s.split_once(':').map_or(s, |(_, code)| code);
+
+        Ok(match code {
+            "m" | "meters" => Self::Meter,
+            "ft" => Self::Foot,
+            "s" | "sec" => Self::Second,
+            "m_s-1" | "m/s" => Self::MeterPerSecond,
+            "km_h-1" | "km/h" => Self::KilometerPerHour,
+            "kt" | "kts" => Self::Knot,
+            "mph" => Self::MilePerHour,
+            "degC" => Self::Celsius,
+            "degF" => Self::Fahrenheit,
+            "degree_(angle)" | "degT" | "degrees" => Self::Degree,
+            "percent" | "%" => Self::Percent,
+            _ => bail!("Unknown unit of measure {s}"),
+        })
+    }
+}
+
+impl Unit {
+    pub fn kind(self) -> Kind {
+        match self {
+            Self::Meter | Self::Foot => Kind::Length,
+            Self::Second => Kind::Duration,
+            Self::MeterPerSecond | Self::KilometerPerHour | Self::Knot | Self::MilePerHour => {
+                Kind::Speed
+            }
+            Self::Celsius | Self::Fahrenheit => Kind::Temperature,
+            Self::Degree => Kind::Angle,
+            Self::Percent => Kind::Percent,
+        }
+    }
+
+    /// Converts a value in this unit into the canonical unit of `kind`.
+    ///
+    /// # Errors
+    /// When the unit measures something other than `kind`, e.g. a wave height
+    /// declared in km/h.
+    pub fn try_to_canonical(self, value: f64, kind: Kind) -> anyhow::Result<f64> {
+        if self.kind() != kind {
+            bail!("{self:?} isn't a unit of {kind:?}");
+        }
+
+        Ok(match self {
+            Self::Foot => Length::from_feet(value).meters(),
+            Self::KilometerPerHour => Speed::from_kilometers_per_hour(value).meters_per_second(),
+            Self::Knot => Speed::from_knots(value).meters_per_second(),
+            Self::MilePerHour => Speed::from_miles_per_hour(value).meters_per_second(),
+            Self::Fahrenheit => Temperature::from_fahrenheit(value).celsius(),
+            Self::Meter
+            | Self::Second
+            | Self::MeterPerSecond
+            | Self::Celsius
+            | Self::Degree
+            | Self::Percent => value,
+        })
+    }
+}
+
+/// The system of units values are displayed in, e.g. `?units=metric`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum System {
+    /// Feet, mph and °F
+    #[default]
+    Imperial,
+    /// Metres, km/h and °C
+    Metric,
+}
+
+/// The unit wind speeds are displayed in, e.g. `?wind=knots`. Defaults to the
+/// system's.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Wind {
+    #[default]
+    Mph,
+    Kmh,
+    Knots,
+    /// The Beaufort force, 0-12, along with its description
+    Beaufort,
+}
+
+/// The units values are displayed in, picked per request.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Units {
+    pub system: System,
+    pub wind: Wind,
+}
+
+/// The labels of the units values are in, sent along with them so clients
+/// don't have to know each system.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+pub struct UnitLabels {
+    pub system: System,
+    pub wind: Wind,
+    /// e.g. "ft"
+    pub height: &'static str,
+    /// e.g. "mph", or "Bft" for the Beaufort force
+    pub speed: &'static str,
+    /// e.g. "°F"
+    pub temperature: &'static str,
+}
+
+impl fmt::Display for System {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Imperial => write!(f, "imperial"),
+            Self::Metric => write!(f, "metric"),
+        }
+    }
+}
+
+impl FromStr for System {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "imperial" => Ok(Self::Imperial),
+            "metric" => Ok(Self::Metric),
+            _ => bail!("Unknown units {s}, expected imperial or metric"),
+        }
+    }
+}
+
+impl fmt::Display for Wind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mph => write!(f, "mph"),
+            Self::Kmh => write!(f, "kmh"),
+            Self::Knots => write!(f, "knots"),
+            Self::Beaufort => write!(f, "beaufort"),
+        }
+    }
+}
+
+impl FromStr for Wind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "mph" => Ok(Self::Mph),
+            "kmh" => Ok(Self::Kmh),
+            "knots" => Ok(Self::Knots),
+            "beaufort" => Ok(Self::Beaufort),
+            _ => bail!("Unknown wind units {s}, expected mph, kmh, knots or beaufort"),
+        }
+    }
+}
+
+/// Written as the system and wind, e.g. "metric-knots", for cache keys and
+/// the cookie.
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.system, self.wind)
+    }
+}
+
+impl FromStr for Units {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.split_once('-') {
+            Some((system, wind)) => Ok(Self::new(system.parse()?, Some(wind.parse()?))),
+            None => Ok(Self::new(s.parse()?, None)),
+        }
+    }
+}
+
+impl Units {
+    pub const IMPERIAL: Self = Self {
+        system: System::Imperial,
+        wind: Wind::Mph,
+    };
+    pub const METRIC: Self = Self {
+        system: System::Metric,
+        wind: Wind::Kmh,
+    };
+
+    /// The system's units, with the wind in its own unit if given.
+    pub fn new(system: System, wind: Option<Wind>) -> Self {
+        let default = match system {
+            System::Imperial => Self::IMPERIAL,
+            System::Metric => Self::METRIC,
+        };
+
+        Self {
+            system,
+            wind: wind.unwrap_or(default.wind),
+        }
+    }
+
+    pub fn labels(self) -> UnitLabels {
+        let (height, temperature) = match self.system {
+            System::Imperial => ("ft", "°F"),
+            System::Metric => ("m", "°C"),
+        };
+        let speed = match self.wind {
+            Wind::Mph => "mph",
+            Wind::Kmh => "km/h",
+            Wind::Knots => "kn",
+            Wind::Beaufort => "Bft",
+        };
+
+        UnitLabels {
+            system: self.system,
+            wind: self.wind,
+            height,
+            speed,
+            temperature,
+        }
+    }
+
+    pub fn height(self, length: Length) -> f64 {
+        match self.system {
+            System::Imperial => length.feet(),
+            System::Metric => length.meters(),
+        }
+    }
+
+    /// The length of a height in these units, the inverse of [`Units::height`].
+    pub fn to_length(self, height: f64) -> Length {
+        match self.system {
+            System::Imperial => Length::from_feet(height),
+            System::Metric => Length::from_meters(height),
+        }
+    }
+
+    pub fn speed(self, speed: Speed) -> f64 {
+        match self.wind {
+            Wind::Mph => speed.miles_per_hour(),
+            Wind::Kmh => speed.kilometers_per_hour(),
+            Wind::Knots => speed.knots(),
+            Wind::Beaufort => f64::from(speed.beaufort().force),
+        }
+    }
+
+    /// Describes the speed when it's shown as a Beaufort force, e.g.
+    /// "Gentle breeze".
+    pub fn describe_speed(self, speed: Speed) -> Option<&'static str> {
+        match self.wind {
+            Wind::Beaufort => Some(speed.beaufort().description),
+            Wind::Mph | Wind::Kmh | Wind::Knots => None,
+        }
+    }
+
+    pub fn temperature(self, temperature: Temperature) -> f64 {
+        match self.system {
+            System::Imperial => temperature.fahrenheit(),
+            System::Metric => temperature.celsius(),
+        }
+    }
+
+    /// The `Set-Cookie` value remembering these units for the pages.
+    pub fn cookie(self) -> String {
+        format!("{UNITS_COOKIE}={self}; Path=/; Max-Age={UNITS_COOKIE_MAX_AGE}; SameSite=Lax")
+    }
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct UnitsParam {
+    pub units: Option<System>,
+    pub wind: Option<Wind>,
+}
+
+/// Extracts the units from the `units` and `wind` query params, falling back
+/// to the units cookie set by the pages, then imperial.
+pub struct UnitsQuery(pub Units);
+
+impl<S> FromRequestParts<S> for UnitsQuery
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let param = match Query::<UnitsParam>::from_request_parts(parts, state).await {
+            Ok(Query(param)) => param,
+            Err(e) => return Err(query_rejection(e)),
+        };
+
+        let cookie = parts
+            .headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, _)| *name == UNITS_COOKIE)
+            .and_then(|(_, value)| value.parse::<Units>().ok());
+
+        // Params override the cookie, the wind only when the system's kept.
+        let units = match (param.units, cookie) {
+            (None, Some(cookie)) => Units::new(cookie.system, param.wind.or(Some(cookie.wind))),
+            (system, _) => Units::new(system.unwrap_or_default(), param.wind),
+        };
+
+        Ok(UnitsQuery(units))
+    }
+}
+
+/// A length, e.g. a wave height.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Length {
+    meters: f64,
+}
+
+impl Length {
+    pub fn from_meters(meters: f64) -> Self {
+        Self { meters }
+    }
+
+    pub fn from_feet(feet: f64) -> Self {
+        Self::from_meters(feet * METERS_PER_FOOT)
+    }
+
+    pub fn meters(self) -> f64 {
+        self.meters
+    }
+
+    pub fn feet(self) -> f64 {
+        self.meters / METERS_PER_FOOT
+    }
+}
+
+/// A speed, e.g. of the wind.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Speed {
+    meters_per_second: f64,
+}
+
+impl Speed {
+    pub fn from_meters_per_second(meters_per_second: f64) -> Self {
+        Self { meters_per_second }
+    }
+
+    pub fn from_kilometers_per_hour(kilometers_per_hour: f64) -> Self {
+        Self::from_meters_per_second(kilometers_per_hour * 1000.0 / SECONDS_PER_HOUR)
+    }
+
+    pub fn from_knots(knots: f64) -> Self {
+        Self::from_meters_per_second(knots * METERS_PER_NAUTICAL_MILE / SECONDS_PER_HOUR)
+    }
+
+    pub fn from_miles_per_hour(miles_per_hour: f64) -> Self {
+        Self::from_meters_per_second(miles_per_hour * METERS_PER_MILE / SECONDS_PER_HOUR)
+    }
+
+    pub fn meters_per_second(self) -> f64 {
+        self.meters_per_second
+    }
+
+    pub fn kilometers_per_hour(self) -> f64 {
+        self.meters_per_second * SECONDS_PER_HOUR / 1000.0
+    }
+
+    pub fn miles_per_hour(self) -> f64 {
+        self.meters_per_second * SECONDS_PER_HOUR / METERS_PER_MILE
+    }
+
+    pub fn knots(self) -> f64 {
+        self.meters_per_second * SECONDS_PER_HOUR / METERS_PER_NAUTICAL_MILE
+    }
+
+    pub fn beaufort(self) -> Beaufort {
+        let force = BEAUFORT_SCALE
+            .iter()
+            .position(|(upper, _)| self.meters_per_second < *upper)
+            .unwrap_or(BEAUFORT_SCALE.len());
+
+        Beaufort {
+            force: force as u8,
+            description: BEAUFORT_SCALE
+                .get(force)
+                .map_or("Hurricane force", |(_, description)| description),
+        }
+    }
+}
+
+/// The upper bound in m/s and description of each Beaufort force below 12.
+const BEAUFORT_SCALE: [(f64, &str); 12] = [
+    (0.5, "Calm"),
+    (1.6, "Light air"),
+    (3.4, "Light breeze"),
+    (5.5, "Gentle breeze"),
+    (8.0, "Moderate breeze"),
+    (10.8, "Fresh breeze"),
+    (13.9, "Strong breeze"),
+    (17.2, "Near gale"),
+    (20.8, "Gale"),
+    (24.5, "Strong gale"),
+    (28.5, "Storm"),
+    (32.7, "Violent storm"),
+];
+
+/// A wind speed on the Beaufort scale.
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Beaufort {
+    /// 0 to 12
+    pub force: u8,
+    /// e.g. "Gentle breeze"
+    pub description: &'static str,
+}
+
+/// A temperature, of the air or water.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Temperature {
+    celsius: f64,
+}
+
+impl Temperature {
+    pub fn from_celsius(celsius: f64) -> Self {
+        Self { celsius }
+    }
+
+    pub fn from_fahrenheit(fahrenheit: f64) -> Self {
+        Self::from_celsius((fahrenheit - 32.0) / 1.8)
+    }
+
+    pub fn celsius(self) -> f64 {
+        self.celsius
+    }
+
+    pub fn fahrenheit(self) -> f64 {
+        self.celsius * 1.8 + 32.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn parses_the_units_of_each_source() {
+        assert_eq!(
+            "wmoUnit:km_h-1".parse::<Unit>().unwrap(),
+            Unit::KilometerPerHour
+        );
+        assert_eq!("nwsUnit:s".parse::<Unit>().unwrap(), Unit::Second);
+        assert_eq!(
+            "wmoUnit:degree_(angle)".parse::<Unit>().unwrap(),
+            Unit::Degree
+        );
+        assert_eq!("m/s".parse::<Unit>().unwrap(), Unit::MeterPerSecond);
+        assert_eq!("degT".parse::<Unit>().unwrap(), Unit::Degree);
+        assert_eq!("degrees".parse::<Unit>().unwrap(), Unit::Degree);
+    }
+
+    #[test]
+    fn unknown_units_are_an_error() {
+        let error = "wmoUnit:furlong_fortnight-1".parse::<Unit>().unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Unknown unit of measure wmoUnit:furlong_fortnight-1"
+        );
+    }
+
+    #[test]
+    fn converts_into_the_canonical_unit() {
+        assert_close(
+            Unit::KilometerPerHour
+                .try_to_canonical(36.0, Kind::Speed)
+                .unwrap(),
+            10.0,
+        );
+        assert_close(
+            Unit::Knot.try_to_canonical(1.0, Kind::Speed).unwrap(),
+            0.514_444_444,
+        );
+        assert_close(
+            Unit::Foot.try_to_canonical(10.0, Kind::Length).unwrap(),
+            3.048,
+        );
+        assert_close(
+            Unit::Fahrenheit
+                .try_to_canonical(212.0, Kind::Temperature)
+                .unwrap(),
+            100.0,
+        );
+        assert_eq!(
+            Unit::Meter.try_to_canonical(1.5, Kind::Length).unwrap(),
+            1.5
+        );
+    }
+
+    #[test]
+    fn units_of_another_kind_are_an_error() {
+        assert!(
+            Unit::KilometerPerHour
+                .try_to_canonical(1.0, Kind::Length)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn units_convert_into_their_system() {
+        let length = Length::from_meters(1.0);
+
+        assert_close(Units::METRIC.height(length), 1.0);
+        assert_close(Units::IMPERIAL.height(length), 3.280_839_895);
+        assert_close(Units::METRIC.to_length(1.0).meters(), 1.0);
+        assert_close(
+            Units::METRIC.speed(Speed::from_meters_per_second(10.0)),
+            36.0,
+        );
+        assert_close(
+            Units::METRIC.temperature(Temperature::from_celsius(15.0)),
+            15.0,
+        );
+        assert_eq!(Units::METRIC.labels().height, "m");
+    }
+
+    #[test]
+    fn wind_can_be_in_its_own_units() {
+        let speed = Speed::from_meters_per_second(5.0);
+        let knots = Units::new(System::Metric, Some(Wind::Knots));
+        let beaufort = Units::new(System::Imperial, Some(Wind::Beaufort));
+
+        assert_close(knots.speed(speed), 9.719_222_462);
+        assert_eq!(knots.labels().speed, "kn");
+        assert_eq!(knots.labels().height, "m");
+        assert_eq!(knots.describe_speed(speed), None);
+        assert_eq!(beaufort.speed(speed), 3.0);
+        assert_eq!(beaufort.describe_speed(speed), Some("Gentle breeze"));
+        assert_eq!(beaufort.labels().height, "ft");
+    }
+
+    #[test]
+    fn speeds_fall_on_the_beaufort_scale() {
+        let force = |v| Speed::from_meters_per_second(v).beaufort();
+
+        assert_eq!(
+            force(0.2),
+            Beaufort {
+                force: 0,
+                description: "Calm"
+            }
+        );
+        assert_eq!(force(0.5).force, 1);
+        assert_eq!(force(13.9).description, "Near gale");
+        assert_eq!(
+            force(40.0),
+            Beaufort {
+                force: 12,
+                description: "Hurricane force"
+            }
+        );
+    }
+
+    #[test]
+    fn units_round_trip_through_the_cookie() {
+        let units = Units::new(System::Metric, Some(Wind::Beaufort));
+
+        assert_eq!(units.to_string(), "metric-beaufort");
+        assert_eq!("metric-beaufort".parse::<Units>().unwrap(), units);
+        assert_eq!("metric".parse::<Units>().unwrap(), Units::METRIC);
+        assert!("metric-furlongs".parse::<Units>().is_err());
+    }
+
+    #[test]
+    fn quantities_convert_to_display_units() {
+        assert_close(Length::from_meters(1.0).feet(), 3.280_839_895);
+        assert_close(
+            Speed::from_meters_per_second(6.0).miles_per_hour(),
+            13.421_617_752,
+        );
+        assert_close(
+            Speed::from_kilometers_per_hour(11.5).miles_per_hour(),
+            7.145_768_711,
+        );
+        assert_close(Temperature::from_celsius(66.0).fahrenheit(), 150.8);
+    }
+}
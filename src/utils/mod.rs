@@ -1,31 +1,55 @@
-use anyhow::anyhow;
-use chrono::{DateTime, Timelike};
-use chrono_tz::US::Central;
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, Days, NaiveDate, TimeDelta, Timelike, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serializer};
 pub mod redis_utils;
 
-pub fn convert_meter_to_feet(value: f64) -> f64 {
-    value * 3.281
+/// Client for NOAA's apis. api.weather.gov requires a user agent identifying
+/// the app.
+pub fn nws_client() -> anyhow::Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .user_agent("GatheringSurf/0.1 (+https://gathering.surf)")
+        .build()?)
 }
 
-pub fn convert_kilo_meter_to_mile(value: f64) -> f64 {
-    value * 0.621
-}
-
-pub fn convert_meter_per_second_to_miles_per_hour(value: &str) -> String {
-    format!("{:.0}", value.parse().unwrap_or(0.0) * 2.2369)
-}
-
-pub fn convert_celsius_to_fahrenheit(value: f64) -> String {
-    format!("{:.0}", value * 1.8 + 32.0)
-}
+/// Parses an ISO 8601 duration, e.g. "P1DT5H" -> 29 hours. Years and months
+/// aren't a fixed length so they're an error.
+pub fn parse_duration(s: &str) -> anyhow::Result<TimeDelta> {
+    let body = s.strip_prefix('P').ok_or(anyhow!("{s} isn't a duration"))?;
+    let (date, time) = match body.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (body, None),
+    };
+
+    if time == Some("") || (date.is_empty() && time.is_none()) {
+        bail!("{s} is missing its length");
+    }
 
-pub fn parse_hour(s: &str) -> anyhow::Result<usize> {
-    if let Some((_, hour)) = s.split_once('T') {
-        let hour = hour.strip_suffix("H").ok_or(anyhow!("no hour found!"))?;
-        return Ok(hour.parse()?);
+    let mut duration = TimeDelta::zero();
+    for (part, units) in [
+        (date, &[('W', 7 * 24 * 3600), ('D', 24 * 3600)][..]),
+        (
+            time.unwrap_or_default(),
+            &[('H', 3600), ('M', 60), ('S', 1)][..],
+        ),
+    ] {
+        let mut rest = part;
+        for &(designator, seconds) in units {
+            if let Some((number, after)) = rest.split_once(designator) {
+                let number = number
+                    .parse::<i64>()
+                    .map_err(|_| anyhow!("{s} has an invalid number of {designator}"))?;
+                duration += TimeDelta::seconds(number * seconds);
+                rest = after;
+            }
+        }
+
+        if !rest.is_empty() {
+            bail!("{s} has an unsupported part {rest}");
+        }
     }
 
-    Err(anyhow!("no hour found!"))
+    Ok(duration)
 }
 
 fn convert_24_to_12_hour(hour: u32) -> String {
@@ -52,22 +76,115 @@ fn convert_24_to_12_hour(hour: u32) -> String {
     format!("{hour} PM")
 }
 
-/// Given a time string, e.g. "2024-09-06T11:00:00+00:00" and a number
-/// of hours to increase to, e.g. 2, returns a display friendly time, e.g.
-/// "Fri 09 AM"
-pub fn increment_time(t: &str, hours: usize) -> anyhow::Result<String> {
-    let time = DateTime::parse_from_str(t, "%+")
-        .unwrap()
-        .with_timezone(&Central)
-        + chrono::Duration::hours(hours as i64);
+/// Labels the hour in the given time zone, e.g. "Fri 09 AM", following its
+/// daylight saving time.
+pub fn hour_label(time: DateTime<Utc>, time_zone: Tz) -> String {
+    let time = time.with_timezone(&time_zone);
+
+    format!(
+        "{} {}",
+        time.format("%a"),
+        convert_24_to_12_hour(time.hour())
+    )
+}
+
+/// Serializes a time as RFC 3339, e.g. "2024-09-06T11:00:00+00:00".
+pub fn serialize_rfc3339<S: Serializer>(time: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&time.to_rfc3339())
+}
+
+/// Deserializes a time serialized by [`serialize_rfc3339`].
+pub fn deserialize_rfc3339<'de, D: Deserializer<'de>>(d: D) -> Result<DateTime<Utc>, D::Error> {
+    let time = String::deserialize(d)?;
 
-    let hour = convert_24_to_12_hour(time.hour());
+    DateTime::parse_from_rfc3339(&time)
+        .map(|time| time.to_utc())
+        .map_err(serde::de::Error::custom)
+}
+
+/// Serializes a series of times as RFC 3339.
+pub fn serialize_rfc3339_series<S: Serializer>(
+    times: &[DateTime<Utc>],
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    s.collect_seq(times.iter().map(DateTime::to_rfc3339))
+}
 
-    let time = time.to_rfc2822();
+/// Great-circle distance in miles between two (latitude, longitude) points
+/// using the haversine formula.
+pub fn great_circle_distance(from: (f64, f64), to: (f64, f64)) -> f64 {
+    const EARTH_RADIUS_MILES: f64 = 3958.8;
 
-    let (day, _) = time.split_once(',').unwrap();
+    let (lat_1, lat_2) = (from.0.to_radians(), to.0.to_radians());
+    let delta_lat = (to.0 - from.0).to_radians();
+    let delta_lon = (to.1 - from.1).to_radians();
 
-    Ok(format!("{day} {hour}"))
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat_1.cos() * lat_2.cos() * (delta_lon / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_MILES * a.sqrt().asin()
+}
+
+/// Smallest angle in degrees, 0 to 180, between two compass bearings.
+pub fn angular_difference(a: f64, b: f64) -> f64 {
+    let difference = (a - b).rem_euclid(360.0);
+
+    if difference > 180.0 {
+        360.0 - difference
+    } else {
+        difference
+    }
+}
+
+/// Sunrise and sunset on the given date at a (latitude, longitude), using the
+/// sunrise equation. None when the sun doesn't rise or set that day.
+pub fn sun_times(
+    date: NaiveDate,
+    (latitude, longitude): (f64, f64),
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    const J2000: f64 = 2451545.0;
+    const UNIX_EPOCH_JULIAN_DAY: f64 = 2440587.5;
+
+    let days_since_j2000 = (date - NaiveDate::from_ymd_opt(2000, 1, 1)?).num_days() as f64;
+
+    let mean_solar_time = days_since_j2000 - longitude / 360.0;
+    let mean_anomaly = (357.5291 + 0.98560028 * mean_solar_time).rem_euclid(360.0);
+    let m = mean_anomaly.to_radians();
+    let center = 1.9148 * m.sin() + 0.02 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+    let ecliptic_longitude = (mean_anomaly + center + 180.0 + 102.9372)
+        .rem_euclid(360.0)
+        .to_radians();
+
+    let transit =
+        J2000 + mean_solar_time + 0.0053 * m.sin() - 0.0069 * (2.0 * ecliptic_longitude).sin();
+
+    let declination = (ecliptic_longitude.sin() * 23.4397_f64.to_radians().sin()).asin();
+    let latitude = latitude.to_radians();
+    // The sun's upper limb touching the horizon, allowing for refraction.
+    let hour_angle = ((-0.833_f64).to_radians().sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+
+    if !(-1.0..=1.0).contains(&hour_angle) {
+        return None;
+    }
+
+    let hour_angle = hour_angle.acos().to_degrees() / 360.0;
+    let to_utc = |julian_day: f64| {
+        DateTime::from_timestamp(((julian_day - UNIX_EPOCH_JULIAN_DAY) * 86400.0) as i64, 0)
+    };
+
+    Some((to_utc(transit - hour_angle)?, to_utc(transit + hour_angle)?))
+}
+
+/// Whether the sun is up at a (latitude, longitude) at the given time.
+pub fn is_daylight(time: DateTime<Utc>, coordinates: (f64, f64)) -> bool {
+    // West of Greenwich the sun sets after midnight UTC, so check the day before too.
+    let date = time.date_naive();
+    [date.checked_sub_days(Days::new(1)), Some(date)]
+        .into_iter()
+        .flatten()
+        .filter_map(|date| sun_times(date, coordinates))
+        .any(|(sunrise, sunset)| sunrise <= time && time < sunset)
 }
 
 /// Limits f64 to two decimal points
@@ -76,47 +193,136 @@ pub fn truncate_to_two_decimals(v: f64) -> f64 {
     (v * 100.0).trunc() / 100.0
 }
 
+/// Rounds f64 to two decimal points
+#[must_use]
+pub fn round_to_two_decimals(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use chrono_tz::US::{Central, Eastern};
+
     #[test]
     fn truncate_to_two_decimals_limits_f64_to_two_decimals() {
         assert_eq!(truncate_to_two_decimals(12.121212), 12.12)
     }
 
     #[test]
-    fn increment_time_creates_display_string() {
+    fn great_circle_distance_is_zero_for_the_same_point() {
+        assert_eq!(great_circle_distance((43.0, -87.8), (43.0, -87.8)), 0.0)
+    }
+
+    #[test]
+    fn great_circle_distance_measures_milwaukee_to_chicago() {
+        let distance = great_circle_distance((43.0389, -87.9065), (41.8781, -87.6298));
+
+        assert!((distance - 81.4).abs() < 0.1, "{distance}")
+    }
+
+    const MILWAUKEE: (f64, f64) = (43.0389, -87.9065);
+
+    #[test]
+    fn sun_times_match_the_almanac_for_milwaukee() {
+        let (sunrise, sunset) =
+            sun_times(NaiveDate::from_ymd_opt(2024, 6, 10).unwrap(), MILWAUKEE).unwrap();
+
+        // 5:13 AM and 8:28 PM CDT
+        let expected_sunrise = "2024-06-10T10:13:00Z".parse::<DateTime<Utc>>().unwrap();
+        let expected_sunset = "2024-06-11T01:28:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        assert!(
+            (sunrise - expected_sunrise).abs() < TimeDelta::minutes(3),
+            "{sunrise}"
+        );
+        assert!(
+            (sunset - expected_sunset).abs() < TimeDelta::minutes(3),
+            "{sunset}"
+        );
+    }
+
+    #[test]
+    fn is_daylight_handles_a_sunset_after_midnight_utc() {
+        let evening = "2024-06-11T01:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let night = "2024-06-11T05:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        assert!(is_daylight(evening, MILWAUKEE));
+        assert!(!is_daylight(night, MILWAUKEE));
+    }
+
+    #[test]
+    fn angular_difference_wraps_around_north() {
+        assert_eq!(angular_difference(350.0, 10.0), 20.0)
+    }
+
+    #[test]
+    fn angular_difference_is_at_most_180() {
+        assert_eq!(angular_difference(90.0, 270.0), 180.0)
+    }
+
+    fn time(t: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(t).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn hour_label_creates_display_string() {
         assert_eq!(
-            increment_time("2024-09-06T11:00:00+00:00", 2).unwrap(),
+            hour_label(time("2024-09-06T13:00:00+00:00"), Central),
             "Fri 08 AM"
         )
     }
 
     #[test]
-    fn increment_time_creates_display_string_with_a_pm_time() {
+    fn hour_label_creates_display_string_with_a_pm_time() {
         assert_eq!(
-            increment_time("2024-09-06T11:00:00+00:00", 12).unwrap(),
+            hour_label(time("2024-09-06T23:00:00+00:00"), Central),
             "Fri 06 PM"
         )
     }
 
     #[test]
-    fn increment_time_creates_handles_noon() {
+    fn hour_label_handles_noon() {
         assert_eq!(
-            increment_time("2024-09-06T11:00:00+00:00", 6).unwrap(),
+            hour_label(time("2024-09-06T17:00:00+00:00"), Central),
             "Fri 12 PM"
         )
     }
 
     #[test]
-    fn increment_time_creates_handles_midnight() {
+    fn hour_label_handles_midnight() {
         assert_eq!(
-            increment_time("2024-09-06T11:00:00+00:00", 18).unwrap(),
+            hour_label(time("2024-09-07T05:00:00+00:00"), Central),
             "Sat 12 AM"
         )
     }
 
+    #[test]
+    fn hour_label_follows_daylight_saving_time() {
+        // Clocks fall back from 2 AM CDT to 1 AM CST on Nov 3rd, 2024.
+        assert_eq!(
+            hour_label(time("2024-11-03T06:00:00+00:00"), Central),
+            "Sun 01 AM"
+        );
+        assert_eq!(
+            hour_label(time("2024-11-03T07:00:00+00:00"), Central),
+            "Sun 01 AM"
+        );
+        assert_eq!(
+            hour_label(time("2024-11-03T08:00:00+00:00"), Central),
+            "Sun 02 AM"
+        );
+    }
+
+    #[test]
+    fn hour_label_is_in_the_given_time_zone() {
+        assert_eq!(
+            hour_label(time("2024-09-06T13:00:00+00:00"), Eastern),
+            "Fri 09 AM"
+        )
+    }
+
     #[test]
     fn convert_24_to_12_hour_adds_a_leading_zero_to_am() {
         assert_eq!(convert_24_to_12_hour(7), "07 AM")
@@ -148,27 +354,22 @@ mod tests {
     }
 
     #[test]
-    fn parse_hour_gets_the_number_of_hours_from_a_period_string() {
-        assert_eq!(parse_hour("T12H").unwrap(), 12)
-    }
-
-    #[test]
-    fn convert_meter_per_second_to_miles_per_hour_converts_correctly() {
-        assert_eq!(convert_meter_per_second_to_miles_per_hour("6.0"), "13")
-    }
-
-    #[test]
-    fn convert_meter_to_feet_converts_correctly() {
-        assert_eq!(convert_meter_to_feet(1.0), 3.281)
+    fn parse_duration_parses_hours() {
+        assert_eq!(parse_duration("PT12H").unwrap(), TimeDelta::hours(12))
     }
 
     #[test]
-    fn convert_kilo_meter_to_mile_converts_correctly() {
-        assert_eq!(convert_kilo_meter_to_mile(11.5), 7.1415)
+    fn parse_duration_parses_days_and_hours() {
+        assert_eq!(parse_duration("P2DT10H").unwrap(), TimeDelta::hours(58));
+        assert_eq!(parse_duration("P1D").unwrap(), TimeDelta::hours(24));
+        assert_eq!(parse_duration("P1W").unwrap(), TimeDelta::days(7));
+        assert_eq!(parse_duration("PT1H30M").unwrap(), TimeDelta::minutes(90));
     }
 
     #[test]
-    fn convert_celsius_to_fahrenheit_converts_correctly() {
-        assert_eq!(convert_celsius_to_fahrenheit(66.0), "151")
+    fn parse_duration_rejects_invalid_durations() {
+        for duration in ["", "P", "PT", "T2H", "P1M", "PT2X", "P1DT", "PxD"] {
+            assert!(parse_duration(duration).is_err(), "{duration}");
+        }
     }
 }
@@ -1,19 +1,41 @@
 use bb8::Pool;
 use bb8_redis::RedisConnectionManager;
 use redis::{AsyncCommands, SetExpiry, SetOptions};
+use tracing::warn;
 
 /// Gets the value from the Redis cache if it exists.
 pub async fn get(key: &str, pool: &Pool<RedisConnectionManager>) -> Option<String> {
     pool.get().await.ok()?.get(key).await.ok()
 }
 
+/// Gets the values for all the keys from the Redis cache in one round trip,
+/// misses and failures are `None`.
+pub async fn get_many(keys: &[String], pool: &Pool<RedisConnectionManager>) -> Vec<Option<String>> {
+    let values = match pool.get().await {
+        Ok(mut conn) => conn.mget(keys).await.ok(),
+        Err(_) => None,
+    };
+
+    values.unwrap_or_else(|| vec![None; keys.len()])
+}
+
 /// Sets the given k,v pair for 5 minutes in the Redis cache.
 pub async fn set(
     key: &str,
     value: &str,
     pool: &Pool<RedisConnectionManager>,
 ) -> anyhow::Result<()> {
-    let opts = SetOptions::default().with_expiration(SetExpiry::EX(300));
+    set_with_expiry(key, value, 300, pool).await
+}
+
+/// Sets the given k,v pair for the given number of seconds in the Redis cache.
+pub async fn set_with_expiry(
+    key: &str,
+    value: &str,
+    seconds: u64,
+    pool: &Pool<RedisConnectionManager>,
+) -> anyhow::Result<()> {
+    let opts = SetOptions::default().with_expiration(SetExpiry::EX(seconds));
     pool.get()
         .await?
         .set_options::<&str, &str, ()>(key, value, opts)
@@ -21,3 +43,22 @@ pub async fn set(
 
     Ok(())
 }
+
+/// Caches the given k,v pair for 5 minutes. The value's still good without
+/// the cache so a failure is only logged.
+pub async fn cache(key: &str, value: &str, pool: &Pool<RedisConnectionManager>) {
+    cache_with_expiry(key, value, 300, pool).await;
+}
+
+/// Caches the given k,v pair for the given number of seconds, only logging a
+/// failure.
+pub async fn cache_with_expiry(
+    key: &str,
+    value: &str,
+    seconds: u64,
+    pool: &Pool<RedisConnectionManager>,
+) {
+    if let Err(e) = set_with_expiry(key, value, seconds, pool).await {
+        warn!("Unable to cache {key}: {e}");
+    }
+}
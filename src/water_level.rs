@@ -0,0 +1,282 @@
+use crate::{AppState, Length, Spot, UnitLabels, Units, nws_client, redis_utils};
+
+use anyhow::{anyhow, bail};
+use chrono::{NaiveDateTime, TimeDelta};
+
+/// Path of the CO-OPS data api on api.tidesandcurrents.noaa.gov.
+pub const WATER_LEVEL_PATH: &str = "/api/prod/datagetter";
+
+/// Hours of water levels used for the trend.
+const TREND_HOURS: i64 = 6;
+/// Change, in feet, over the trend hours under which the level is steady.
+const STEADY_CHANGE: f64 = 0.1;
+/// Swing, in feet, within an hour that's likely a seiche.
+const SEICHE_SWING: f64 = 0.5;
+
+#[derive(serde::Deserialize)]
+struct CoopsResponse {
+    data: Option<Vec<CoopsReading>>,
+    error: Option<CoopsError>,
+}
+
+#[derive(serde::Deserialize)]
+struct CoopsReading {
+    /// GMT time, e.g. "2024-06-10 21:00"
+    t: String,
+    /// Feet, empty when the station didn't report
+    v: String,
+}
+
+#[derive(serde::Deserialize)]
+struct CoopsError {
+    message: String,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Trend {
+    Rising,
+    Falling,
+    Steady,
+}
+
+/// The lake level at a spot's NOAA CO-OPS water level station.
+#[derive(serde::Serialize, Debug)]
+pub struct WaterLevel {
+    pub station: &'static str,
+    pub observed_at: String,
+    /// Height above the IGLD 1985 datum
+    pub level: f64,
+    /// Change over the last 6 hours
+    pub change: f64,
+    /// Steady under a change of 0.1 feet, whatever the units
+    pub trend: Trend,
+    /// The biggest change within any hour of the last 6
+    pub hourly_swing: f64,
+    /// Whether the level swings 0.5 feet or more within an hour, whatever the
+    /// units
+    pub seiche_alert: bool,
+    /// The units the values are in
+    pub units: UnitLabels,
+}
+
+impl WaterLevel {
+    /// Checks for the water level in the Redis cache, if not found gets it
+    /// from CO-OPS and updates the cache.
+    ///
+    /// # Errors
+    /// As [`WaterLevel::try_get`].
+    pub async fn try_get_string(
+        spot: &Spot,
+        state: &AppState,
+        units: Units,
+    ) -> anyhow::Result<String> {
+        let cache_key = format!("water-level-{}-{units}", spot.location.slug);
+        if let Some(data) = redis_utils::get(&cache_key, &state.redis_pool).await {
+            tracing::info!("redis cache hit!");
+            return Ok(data);
+        }
+
+        let data = Self::try_get(spot, state.water_level_url, units).await?;
+        let data = serde_json::to_string(&data)?;
+
+        redis_utils::cache(&cache_key, &data, &state.redis_pool).await;
+
+        Ok(data)
+    }
+
+    /// Gets the last 6 hours of water levels at the spot's station, `None`
+    /// when the spot doesn't have one.
+    ///
+    /// # Errors
+    /// When CO-OPS can't be reached or responds with an error.
+    pub async fn try_get(
+        spot: &Spot,
+        water_level_url: &str,
+        units: Units,
+    ) -> anyhow::Result<Option<Self>> {
+        let Some(station) = spot.water_level_station else {
+            return Ok(None);
+        };
+
+        let response = nws_client()?
+            .get(format!(
+                "{water_level_url}{WATER_LEVEL_PATH}?product=water_level&station={station}&range={TREND_HOURS}&datum=IGLD&units=english&time_zone=gmt&format=json&application=GatheringSurf"
+            ))
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            bail!("Non 200 response from NOAA CO-OPS");
+        }
+
+        Self::try_parse(station, &response.text().await?, units).map(Some)
+    }
+
+    fn try_parse(station: &'static str, data: &str, units: Units) -> anyhow::Result<Self> {
+        let response = serde_json::from_str::<CoopsResponse>(data)?;
+
+        if let Some(error) = response.error {
+            bail!("NOAA CO-OPS error for {station}: {}", error.message);
+        }
+
+        let readings = response
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            // Readings the station didn't report are empty
+            .filter(|reading| !reading.v.is_empty())
+            .map(|reading| {
+                Ok((
+                    NaiveDateTime::parse_from_str(&reading.t, "%Y-%m-%d %H:%M")?.and_utc(),
+                    reading.v.parse::<f64>()?,
+                ))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let (observed_at, level) = *readings
+            .last()
+            .ok_or(anyhow!("NOAA CO-OPS has no water levels for {station}"))?;
+        let (_, earliest) = readings[0];
+
+        let change = level - earliest;
+        let trend = if change.abs() < STEADY_CHANGE {
+            Trend::Steady
+        } else if change > 0.0 {
+            Trend::Rising
+        } else {
+            Trend::Falling
+        };
+
+        let hourly_swing = readings
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (time, level))| {
+                readings[..i]
+                    .iter()
+                    .filter(move |(earlier, _)| *time - *earlier <= TimeDelta::hours(1))
+                    .map(move |(_, earlier_level)| (level - earlier_level).abs())
+            })
+            .fold(0.0, f64::max);
+
+        Ok(Self {
+            station,
+            observed_at: observed_at.to_rfc3339(),
+            level: round_to_thousandth(units.height(Length::from_feet(level))),
+            change: round_to_thousandth(units.height(Length::from_feet(change))),
+            trend,
+            hourly_swing: round_to_thousandth(units.height(Length::from_feet(hourly_swing))),
+            seiche_alert: hourly_swing >= SEICHE_SWING,
+            units: units.labels(),
+        })
+    }
+}
+
+fn round_to_thousandth(v: f64) -> f64 {
+    (v * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn readings(levels: &[f64]) -> String {
+        let data = levels
+            .iter()
+            .enumerate()
+            .map(|(i, level)| {
+                serde_json::json!({
+                    "t": format!("2024-06-10 {:02}:{:02}", 16 + i / 10, i % 10 * 6),
+                    "v": format!("{level:.3}"),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::json!({ "data": data }).to_string()
+    }
+
+    #[test]
+    fn parses_the_level_and_trend() {
+        let level = WaterLevel::try_parse(
+            "9087057",
+            include_str!("../tests/fixtures/coops/9087057.json"),
+            Units::IMPERIAL,
+        )
+        .unwrap();
+
+        assert_eq!(level.observed_at, "2024-06-10T22:00:00+00:00");
+        assert_eq!(level.level, 579.575);
+        assert_eq!(level.change, 0.163);
+        assert_eq!(level.trend, Trend::Rising);
+        assert!(!level.seiche_alert);
+    }
+
+    #[test]
+    fn metric_levels_are_in_meters() {
+        let level = WaterLevel::try_parse(
+            "9087057",
+            include_str!("../tests/fixtures/coops/9087057.json"),
+            Units::METRIC,
+        )
+        .unwrap();
+
+        assert_eq!(level.level, 176.654);
+        assert_eq!(level.change, 0.05);
+        assert_eq!(level.units.height, "m");
+        assert_eq!(level.trend, Trend::Rising);
+    }
+
+    #[test]
+    fn a_quick_swing_is_a_seiche() {
+        let level = WaterLevel::try_parse(
+            "9087057",
+            &readings(&[579.5, 579.5, 579.8, 580.1, 579.7, 579.3, 579.5]),
+            Units::IMPERIAL,
+        )
+        .unwrap();
+
+        assert_eq!(level.hourly_swing, 0.8);
+        assert!(level.seiche_alert);
+        assert_eq!(level.trend, Trend::Steady);
+    }
+
+    #[test]
+    fn a_metric_seiche_is_found_in_feet() {
+        let level = WaterLevel::try_parse(
+            "9087057",
+            &readings(&[579.5, 579.5, 579.8, 580.1, 579.7, 579.3, 579.5]),
+            Units::METRIC,
+        )
+        .unwrap();
+
+        assert_eq!(level.hourly_swing, 0.244);
+        assert!(level.seiche_alert);
+        assert_eq!(level.trend, Trend::Steady);
+    }
+
+    #[test]
+    fn swings_over_more_than_an_hour_arent_a_seiche() {
+        // Rising a fiftieth of a foot each half hour is a steady rise
+        let levels = (0..61)
+            .map(|i| 579.5 + 0.02 * (i / 5) as f64)
+            .collect::<Vec<_>>();
+
+        let level = WaterLevel::try_parse("9087057", &readings(&levels), Units::IMPERIAL).unwrap();
+
+        assert!(!level.seiche_alert);
+        assert_eq!(level.trend, Trend::Rising);
+    }
+
+    #[test]
+    fn errors_from_coops_are_errors() {
+        let error = WaterLevel::try_parse(
+            "9087057",
+            r#"{"error": {"message": "No data was found."}}"#,
+            Units::IMPERIAL,
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("No data was found."));
+    }
+}
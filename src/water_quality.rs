@@ -38,7 +38,7 @@ impl WaterQuality {
 
     async fn try_get(spot: Arc<Spot>, quality_url: &'static str) -> anyhow::Result<Self> {
         let (water_quality, water_quality_text) =
-            Self::get_quality_data(spot.quality_query, spot.status_query, quality_url).await?;
+            Self::get_quality_data(&spot.quality_query, &spot.status_query, quality_url).await?;
 
         Ok(Self {
             water_quality,
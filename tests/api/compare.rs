@@ -0,0 +1,84 @@
+use crate::{helpers::TestApp, mock_app, mocked_happy_path_test_app};
+
+#[tokio::test]
+async fn it_compares_the_requested_spots() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/api/compare?spots=Atwater", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["spots"].as_array().unwrap().len(), 1);
+    assert_eq!(data["spots"][0]["slug"], "atwater");
+    assert_eq!(data["ranked"][0]["slug"], "atwater");
+    assert_eq!(data["ranked"][0]["quality_text"], "Good");
+
+    let hours = data["hours"].as_array().unwrap();
+    assert_eq!(hours.len(), 18);
+    assert_eq!(hours[0]["label"], "Mon 03 PM");
+    assert_eq!(hours[0]["conditions"][0]["wave_height"], 1.0);
+    assert_eq!(data["units"]["height"], "ft");
+}
+
+#[tokio::test]
+async fn it_compares_the_spots_in_metric() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!(
+        "http://{}/api/compare?spots=atwater&units=metric",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["units"]["height"], "m");
+    assert!(
+        data["hours"][0]["conditions"][0]["wave_height"]
+            .as_f64()
+            .unwrap()
+            < 1.0
+    );
+}
+
+#[tokio::test]
+async fn it_rejects_an_unknown_spot_to_compare() {
+    let app = mock_app!();
+
+    let response = reqwest::get(format!(
+        "http://{}/api/compare?spots=atwater,nowhere",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 400);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert!(data["details"].as_str().unwrap().contains("nowhere"));
+}
+
+#[tokio::test]
+async fn it_renders_the_comparison_page() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/compare?spots=atwater", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = response.text().await.unwrap();
+
+    assert!(response.contains("Best Right Now"));
+    assert!(response.contains("Atwater"));
+    assert!(response.contains("Mon 03 PM"));
+}
@@ -1,4 +1,4 @@
-use crate::{helpers::TestApp, mocked_happy_path_test_app, mocked_unhappy_path_test_app};
+use crate::{helpers::TestApp, mock_app, mocked_happy_path_test_app, mocked_unhappy_path_test_app};
 
 #[tokio::test]
 async fn it_returns_the_forecast_data_as_json() {
@@ -20,6 +20,38 @@ async fn it_returns_the_forecast_data_as_json() {
     insta::assert_snapshot!(data);
 }
 
+#[tokio::test]
+async fn it_returns_the_forecast_sessions_as_json() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!(
+        "http://{}/api/forecast/sessions?spot=atwater",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["spot"], "atwater");
+    assert!(data["sessions"].as_array().unwrap().is_empty());
+    assert!(data["best"].is_null());
+
+    // No hour of the mocked forecast is good enough for a session
+    let forecast = reqwest::get(format!("http://{}/api/forecast?spot=atwater", &app.addr))
+        .await
+        .unwrap()
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+    let scores = forecast["scores"].as_array().unwrap();
+
+    assert!(!scores.is_empty());
+    assert!(scores.iter().all(|score| score["label"] == "Very Poor"));
+}
+
 #[tokio::test]
 async fn it_handles_a_non_200_response_from_forecast_client_and_retries_once() {
     let app = mocked_unhappy_path_test_app!(forecast);
@@ -36,6 +68,21 @@ async fn it_handles_a_non_200_response_from_forecast_client_and_retries_once() {
     assert!(response.contains("Something went wrong: Non 200 response from NOAA"));
 }
 
+#[tokio::test]
+async fn it_returns_an_error_when_the_gridpoint_cannot_be_resolved() {
+    let app = mock_app!();
+
+    let response = reqwest::get(format!("http://{}/api/forecast", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 500);
+
+    let response = response.text().await.unwrap();
+
+    assert!(response.contains("Unable to resolve the NWS gridpoint for Atwater"));
+}
+
 #[tokio::test]
 async fn forecast_integration_test() {
     let app = TestApp::try_new()
@@ -55,3 +102,43 @@ async fn forecast_integration_test() {
     assert!(response.contains("as_of"));
     assert!(response.contains("quality"));
 }
+
+#[tokio::test]
+async fn it_returns_the_marine_forecast_and_active_alerts() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/api/forecast?spot=atwater", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["marine_forecast"]["zone"], "LMZ644");
+    assert_eq!(data["marine_forecast"]["periods"][1]["name"], "Tonight");
+    assert_eq!(data["alerts"][0]["event"], "Beach Hazards Statement");
+    assert_eq!(data["alerts"][0]["severity"], "Moderate");
+}
+
+#[tokio::test]
+async fn it_returns_the_glcfs_waves_alongside_the_nws_forecast() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/api/forecast?spot=atwater", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+    let glcfs = &data["alternates"][0];
+
+    assert_eq!(glcfs["source"], "GLCFS");
+    assert_eq!(
+        glcfs["wave_height"].as_array().unwrap().len(),
+        data["wave_height"].as_array().unwrap().len()
+    );
+    assert_eq!(glcfs["wave_height"][0], 1.44);
+    assert!(glcfs["wave_height"][4].is_null());
+}
@@ -1,13 +1,25 @@
-use gathering_surf::{ATWATER_PATH, ATWATER_REALTIME_PATH, Settings, get_configuration, startup};
+use gathering_surf::{Settings, get_configuration, startup};
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
 use wiremock::{
     Mock, MockServer, ResponseTemplate,
-    matchers::{method, path},
+    matchers::{method, path, query_param},
 };
 
 use crate::mocks;
 
+// Paths of the default spot, Atwater, in `config/spots.yml`.
+const ATWATER_POINTS_PATH: &str = "/points/43.0894,-87.8758";
+const ATWATER_PATH: &str = "/gridpoints/MKX/90,67";
+const ATWATER_REALTIME_PATH: &str = "/data/realtime2/45013.txt";
+const ATWATER_SPEC_PATH: &str = "/data/realtime2/45013.spec";
+const ATWATER_MARINE_FORECAST_PATH: &str = "/zones/forecast/LMZ644/forecast";
+const ALERTS_PATH: &str = "/alerts/active";
+const SURF_ZONE_PRODUCTS_PATH: &str = "/products/types/SRF/locations/MKX";
+const SURF_ZONE_PRODUCT_PATH: &str = "/products/6b1e0d3c-5a52-4bd6-9d0c-7f1f0c3a2e11";
+const WATER_LEVEL_PATH: &str = "/api/prod/datagetter";
+const ATWATER_GLCFS_PATH: &str = "/erddap/griddap/glcfs_michigan_waves.json";
+
 #[derive(Debug)]
 pub(crate) struct TestApp {
     pub(crate) addr: SocketAddr,
@@ -34,6 +46,8 @@ impl TestApp {
             // Override the API urls with the mock servers' urls
             config.forecast_api.base_url = mock_client.uri();
             config.realtime_api.base_url = mock_client.uri();
+            config.water_level_api.base_url = mock_client.uri();
+            config.glcfs_api.base_url = mock_client.uri();
 
             config
         });
@@ -46,7 +60,23 @@ impl TestApp {
         })
     }
 
+    pub async fn attach_points_mocks(&self) {
+        if let Some(client) = &self.mock_client {
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(ATWATER_POINTS_PATH))
+                        .respond_with(
+                            ResponseTemplate::new(200).set_body_json(mocks::points_json()),
+                        ),
+                )
+                .await;
+        }
+    }
+
     pub async fn attach_success_mocks(&self) {
+        self.attach_points_mocks().await;
+
         if let Some(client) = &self.mock_client {
             client
                 .register(
@@ -58,6 +88,59 @@ impl TestApp {
                 )
                 .await;
 
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(ATWATER_MARINE_FORECAST_PATH))
+                        .respond_with(
+                            ResponseTemplate::new(200).set_body_json(mocks::marine_forecast_json()),
+                        ),
+                )
+                .await;
+
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(ALERTS_PATH))
+                        .and(query_param("zone", "LMZ644,WIZ066"))
+                        .respond_with(
+                            ResponseTemplate::new(200).set_body_json(mocks::alerts_json()),
+                        ),
+                )
+                .await;
+
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(ATWATER_GLCFS_PATH))
+                        .respond_with(
+                            ResponseTemplate::new(200).set_body_string(mocks::GLCFS_RESPONSE),
+                        ),
+                )
+                .await;
+
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(SURF_ZONE_PRODUCTS_PATH))
+                        .respond_with(
+                            ResponseTemplate::new(200)
+                                .set_body_json(mocks::surf_zone_products_json()),
+                        ),
+                )
+                .await;
+
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(SURF_ZONE_PRODUCT_PATH))
+                        .respond_with(
+                            ResponseTemplate::new(200)
+                                .set_body_json(mocks::surf_zone_product_json()),
+                        ),
+                )
+                .await;
+
             client
                 .register(
                     Mock::given(method("GET"))
@@ -67,6 +150,40 @@ impl TestApp {
                         ),
                 )
                 .await;
+
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(ATWATER_SPEC_PATH))
+                        .respond_with(
+                            ResponseTemplate::new(200)
+                                .set_body_string(mocks::REALTIME_SPEC_RESPONSE),
+                        ),
+                )
+                .await;
+
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(WATER_LEVEL_PATH))
+                        .and(query_param("station", "9087057"))
+                        .respond_with(
+                            ResponseTemplate::new(200).set_body_string(mocks::WATER_LEVEL_RESPONSE),
+                        ),
+                )
+                .await;
+        }
+    }
+
+    pub async fn attach_realtime_mock(&self, body: &'static str) {
+        if let Some(client) = &self.mock_client {
+            client
+                .register(
+                    Mock::given(method("GET"))
+                        .and(path(ATWATER_REALTIME_PATH))
+                        .respond_with(ResponseTemplate::new(200).set_body_string(body)),
+                )
+                .await;
         }
     }
 
@@ -84,6 +201,8 @@ impl TestApp {
     }
 
     pub async fn attach_failed_forecast_request_mocks(&self) {
+        self.attach_points_mocks().await;
+
         if let Some(client) = &self.mock_client {
             client
                 .register(
@@ -1,8 +1,18 @@
+mod compare;
 mod forecast;
 mod glimpse;
 mod health_check;
+#[allow(
+    clippy::crate_in_macro_def,
+    clippy::let_underscore_future,
+    clippy::needless_borrow
+)]
 mod helpers;
+// Values are as precise as the NWS serves them
+#[allow(clippy::excessive_precision)]
 mod mocks;
 mod not_found;
 mod realtime;
 mod root;
+mod spots;
+mod water_level;
@@ -58,6 +58,7 @@ pub fn forecast_json() -> &'static serde_json::Value {
                     "value": 30
                 }]},
                  "probabilityOfThunder": {
+            "uom": "wmoUnit:percent",
             "values": [
                 {
                     "validTime": "2024-06-10T20:00:00+00:00/PT20H",
@@ -109,6 +110,48 @@ pub fn forecast_json() -> &'static serde_json::Value {
     })
 }
 
+pub fn points_json() -> &'static serde_json::Value {
+    static POINTS_JSON: OnceLock<serde_json::Value> = OnceLock::new();
+    POINTS_JSON.get_or_init(|| {
+        json!({
+        "@context": [
+            "https://geojson.org/geojson-ld/geojson-context.jsonld",
+            {
+                "@version": "1.1",
+                "wx": "https://api.weather.gov/ontology#",
+                "s": "https://schema.org/",
+                "geo": "http://www.opengis.net/ont/geosparql#",
+                "unit": "http://codes.wmo.int/common/unit/",
+                "@vocab": "https://api.weather.gov/ontology#"
+            }
+        ],
+        "id": "https://api.weather.gov/points/43.0894,-87.8758",
+        "type": "Feature",
+        "geometry": {
+            "type": "Point",
+            "coordinates": [-87.8758, 43.0894]
+        },
+        "properties": {
+            "@id": "https://api.weather.gov/points/43.0894,-87.8758",
+            "@type": "wx:Point",
+            "cwa": "MKX",
+            "forecastOffice": "https://api.weather.gov/offices/MKX",
+            "gridId": "MKX",
+            "gridX": 90,
+            "gridY": 67,
+            "forecast": "https://api.weather.gov/gridpoints/MKX/90,67/forecast",
+            "forecastHourly": "https://api.weather.gov/gridpoints/MKX/90,67/forecast/hourly",
+            "forecastGridData": "https://api.weather.gov/gridpoints/MKX/90,67",
+            "observationStations": "https://api.weather.gov/gridpoints/MKX/90,67/stations",
+            "forecastZone": "https://api.weather.gov/zones/forecast/WIZ066",
+            "county": "https://api.weather.gov/zones/county/WIC079",
+            "fireWeatherZone": "https://api.weather.gov/zones/fire/WIZ066",
+            "timeZone": "America/Chicago",
+            "radarStation": "KMKX"
+        }})
+    })
+}
+
 pub const REALTIME_RESPONSE: &str = r#"#YY  MM DD hh mm WDIR WSPD GST  WVHT   DPD   APD MWD   PRES  ATMP  WTMP  DEWP  VIS PTDY  TIDE
 #yr  mo dy hr mn degT m/s  m/s     m   sec   sec degT   hPa  degC  degC  degC  nmi  hPa    ft
 2025 05 23 18 30  90  2.0  3.0   0.3     5    MM  24 1020.4   7.7   7.7    MM   MM   MM    MM
@@ -163,3 +206,112 @@ pub const REALTIME_RESPONSE: &str = r#"#YY  MM DD hh mm WDIR WSPD GST  WVHT   DP
 2025 05 22 18 00  40  2.0  3.0   0.4     4    MM  25 1016.3   7.5   6.9    MM   MM +1.3    MM
 2025 05 22 17 30  50  4.0  5.0   0.4     4    MM  24 1016.1   7.7   6.8    MM   MM   MM    MM
 2025 05 22 17 00  50  5.0  7.0   0.4     4    MM  21 1016.0   7.6   6.8    MM   MM +1.5    MM"#;
+
+/// A bouy without wind or temperature sensors.
+pub const REALTIME_WAVES_ONLY_RESPONSE: &str = r#"#YY  MM DD hh mm WDIR WSPD GST  WVHT   DPD   APD MWD   PRES  ATMP  WTMP  DEWP  VIS PTDY  TIDE
+#yr  mo dy hr mn degT m/s  m/s     m   sec   sec degT   hPa  degC  degC  degC  nmi  hPa    ft
+2025 05 23 18 30  MM   MM   MM   0.6     5    MM  24     MM    MM    MM    MM   MM   MM    MM
+2025 05 23 18 00  MM   MM   MM   0.6     5    MM  22     MM    MM    MM    MM   MM   MM    MM
+"#;
+
+pub const REALTIME_SPEC_RESPONSE: &str = r#"#YY  MM DD hh mm WVHT  SwH  SwP  WWH  WWP SwD WWD  STEEPNESS  APD MWD
+#yr  mo dy hr mn    m    m  sec    m  sec  -  degT     -      sec degT
+2025 05 23 18 30  0.3  0.2  6.7  0.2  3.3 NNE  NNE       AVERAGE  3.6  24
+2025 05 23 18 00  0.3  0.2  6.7  0.2  3.4 NNE  NNE       AVERAGE  3.6  22
+2025 05 23 17 30  0.2  0.1  7.1  0.2  3.2  NE  NNE         STEEP  3.4  19
+"#;
+
+pub const WATER_LEVEL_RESPONSE: &str = include_str!("../../fixtures/coops/9087057.json");
+
+pub const GLCFS_RESPONSE: &str = include_str!("../../fixtures/glcfs/atwater.json");
+
+pub fn marine_forecast_json() -> &'static serde_json::Value {
+    static MARINE_FORECAST_JSON: OnceLock<serde_json::Value> = OnceLock::new();
+    MARINE_FORECAST_JSON.get_or_init(|| {
+        json!({
+        "@context": {
+            "@version": "1.1"
+        },
+        "type": "Feature",
+        "geometry": null,
+        "properties": {
+            "zone": "https://api.weather.gov/zones/forecast/LMZ644",
+            "updated": "2025-05-23T14:22:00+00:00",
+            "periods": [
+                {
+                    "number": 1,
+                    "name": "Today",
+                    "detailedForecast": "Northeast wind 5 to 10 kt. Waves 1 to 2 ft."
+                },
+                {
+                    "number": 2,
+                    "name": "Tonight",
+                    "detailedForecast": "North wind 10 to 15 kt. Waves 2 to 4 ft."
+                }
+            ]
+        }})
+    })
+}
+
+pub fn alerts_json() -> &'static serde_json::Value {
+    static ALERTS_JSON: OnceLock<serde_json::Value> = OnceLock::new();
+    ALERTS_JSON.get_or_init(|| {
+        json!({
+        "type": "FeatureCollection",
+        "features": [
+            {
+                "id": "https://api.weather.gov/alerts/urn:oid:2.49.0.1.840.0.1",
+                "type": "Feature",
+                "geometry": null,
+                "properties": {
+                    "areaDesc": "Milwaukee",
+                    "onset": "2025-05-23T15:00:00-05:00",
+                    "ends": "2025-05-24T04:00:00-05:00",
+                    "severity": "Moderate",
+                    "event": "Beach Hazards Statement",
+                    "headline": "Beach Hazards Statement issued May 23 at 9:22AM CDT until May 24 at 4:00AM CDT by NWS Milwaukee/Sullivan WI",
+                    "description": "* WHAT...Dangerous swimming conditions. Waves 3 to 5 feet.",
+                    "instruction": "Stay out of the water and off of piers."
+                }
+            }
+        ],
+        "title": "Current watches, warnings, and advisories"
+        })
+    })
+}
+
+pub fn surf_zone_products_json() -> &'static serde_json::Value {
+    static SURF_ZONE_PRODUCTS_JSON: OnceLock<serde_json::Value> = OnceLock::new();
+    SURF_ZONE_PRODUCTS_JSON.get_or_init(|| {
+        json!({
+        "@context": {
+            "@version": "1.1"
+        },
+        "@graph": [
+            {
+                "id": "6b1e0d3c-5a52-4bd6-9d0c-7f1f0c3a2e11",
+                "wmoCollectiveId": "FZUS53",
+                "issuingOffice": "KMKX",
+                "issuanceTime": "2025-05-23T14:02:00+00:00",
+                "productCode": "SRF",
+                "productName": "Surf Zone Forecast"
+            }
+        ]
+        })
+    })
+}
+
+pub fn surf_zone_product_json() -> &'static serde_json::Value {
+    static SURF_ZONE_PRODUCT_JSON: OnceLock<serde_json::Value> = OnceLock::new();
+    SURF_ZONE_PRODUCT_JSON.get_or_init(|| {
+        json!({
+            "id": "6b1e0d3c-5a52-4bd6-9d0c-7f1f0c3a2e11",
+            "wmoCollectiveId": "FZUS53",
+            "issuingOffice": "KMKX",
+            "issuanceTime": "2025-05-23T14:02:00+00:00",
+            "productCode": "SRF",
+            "productName": "Surf Zone Forecast",
+            "productText": include_str!("../../fixtures/nws/SRFMKX.txt")
+        })
+    })
+}
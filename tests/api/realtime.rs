@@ -1,6 +1,6 @@
 use crate::{
-    helpers::TestApp, integration_test_app, mocked_happy_path_test_app,
-    mocked_unhappy_path_test_app,
+    helpers::TestApp, integration_test_app, mock_app, mocked_happy_path_test_app,
+    mocked_unhappy_path_test_app, mocks,
 };
 
 #[tokio::test]
@@ -55,6 +55,162 @@ async fn it_returns_the_realtime_data_as_json() {
     assert!(data.contains("air_temp"));
     assert!(data.contains("quality_color"));
     assert!(data.contains("quality_text"));
+    assert!(data.contains(r#""rip_current":{"risk":"moderate","source":"nws"}"#));
 
     insta::assert_snapshot!(data);
 }
+
+#[tokio::test]
+async fn it_leaves_readings_no_station_reported_empty() {
+    let app = mock_app!();
+    app.attach_realtime_mock(mocks::REALTIME_WAVES_ONLY_RESPONSE)
+        .await;
+
+    let response = reqwest::get(format!("http://{}/api/realtime", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert!(data["wind_direction"].is_null());
+    assert!(data["wind_speed"].is_null());
+    assert!(data["gusts"].is_null());
+    assert!(data["water_temp"].is_null());
+    assert!(data["air_temp"].is_null());
+    assert_eq!(data["wave_height"], 1.97);
+    assert_eq!(data["quality_reason"]["rule"], "unknown_wind");
+}
+
+#[tokio::test]
+async fn it_returns_the_realtime_data_in_the_requested_units() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/api/realtime?units=metric", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["units"]["system"], "metric");
+    assert_eq!(data["units"]["speed"], "km/h");
+    assert!(data["wind_speed"].is_number());
+}
+
+#[tokio::test]
+async fn it_returns_the_wind_as_a_beaufort_force() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!(
+        "http://{}/api/realtime?units=metric&wind=beaufort",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["units"]["height"], "m");
+    assert_eq!(data["units"]["speed"], "Bft");
+    assert!(data["wind_speed"].as_f64().unwrap() <= 12.0);
+    assert!(data["wind_description"].is_string());
+}
+
+#[tokio::test]
+async fn it_reads_the_units_from_the_cookie() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::Client::new()
+        .get(format!("http://{}/api/realtime", &app.addr))
+        .header("Cookie", "units=metric")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["units"]["system"], "metric");
+}
+
+#[tokio::test]
+async fn it_rejects_unknown_units() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/api/realtime?units=furlongs", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn it_rejects_an_unknown_spot_with_the_valid_spots() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/api/realtime?spot=Malibu", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 400);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert!(data["details"].as_str().unwrap().contains("Malibu"));
+    assert!(
+        data["valid_spots"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("atwater"))
+    );
+}
+
+#[tokio::test]
+async fn it_returns_the_realtime_history_as_json() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!(
+        "http://{}/api/realtime/history?spot=atwater&hours=1",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["station"], "45013");
+    assert_eq!(data["hours"], 1);
+
+    let observations = data["observations"].as_array().unwrap();
+    assert_eq!(observations.len(), 3);
+    assert_eq!(observations[0]["observed_at"], "2025-05-23T17:30:00+00:00");
+    assert_eq!(observations[2]["observed_at"], "2025-05-23T18:30:00+00:00");
+    assert_eq!(observations[2]["wind_speed"], 4.0);
+}
+
+#[tokio::test]
+async fn it_rejects_bad_history_hours_as_json() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!(
+        "http://{}/api/realtime/history?spot=atwater&hours=yesterday",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 400);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["error"], "Invalid query parameter");
+    assert!(data["details"].as_str().unwrap().contains("hours"));
+}
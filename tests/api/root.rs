@@ -1,4 +1,4 @@
-use crate::helpers::TestApp;
+use crate::{helpers::TestApp, mocked_happy_path_test_app};
 
 #[tokio::test]
 async fn it_returns_the_index() {
@@ -15,3 +15,37 @@ async fn it_returns_the_index() {
     let response = response.text().await.unwrap();
     assert!(response.contains("gathering surf"));
 }
+
+#[tokio::test]
+async fn it_remembers_the_requested_units() {
+    let app = TestApp::try_new_mocked()
+        .await
+        .expect("Unable to start test server.");
+
+    let response = reqwest::get(format!("http://{}/?units=metric", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let cookie = response.headers()["set-cookie"].to_str().unwrap();
+    assert!(cookie.starts_with("units=metric-kmh;"));
+
+    let response = response.text().await.unwrap();
+    assert!(response.contains("°C"));
+}
+
+#[tokio::test]
+async fn it_streams_the_sessions_with_the_forecast() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = response.text().await.unwrap();
+    assert!(response.contains(r#"id="sessions-data""#));
+    assert!(response.contains(r#"id="forecast-complete""#));
+}
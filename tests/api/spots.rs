@@ -0,0 +1,93 @@
+use crate::{helpers::TestApp, mock_app};
+
+#[tokio::test]
+async fn it_returns_the_spots_ordered_by_distance() {
+    let app = mock_app!();
+
+    // Just off the Racine harbor
+    let response = reqwest::get(format!(
+        "http://{}/api/spots/nearest?lat=42.73&lon=-87.77",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+    let spots = data.as_array().unwrap();
+
+    assert_eq!(spots.first().unwrap()["slug"], "racine");
+    assert_eq!(spots.last().unwrap()["slug"], "sheboygan-north");
+    assert!(
+        spots
+            .windows(2)
+            .all(|w| w[0]["distance"].as_f64() <= w[1]["distance"].as_f64())
+    );
+}
+
+#[tokio::test]
+async fn it_rejects_coordinates_out_of_range() {
+    let app = mock_app!();
+
+    let response = reqwest::get(format!(
+        "http://{}/api/spots/nearest?lat=142.73&lon=-87.77",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn it_returns_every_spot_as_json() {
+    let app = mock_app!();
+
+    let response = reqwest::get(format!("http://{}/api/spots", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+    let atwater = &data.as_array().unwrap()[0];
+
+    assert_eq!(atwater["name"], "Atwater");
+    assert_eq!(atwater["bouy_id"], "45013");
+    assert_eq!(atwater["station_id"], "MLWW3");
+    assert_eq!(atwater["has_bouy"], true);
+}
+
+#[tokio::test]
+async fn it_returns_every_spot_as_geojson() {
+    let app = mock_app!();
+
+    let response = reqwest::get(format!("http://{}/api/spots.geojson", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["content-type"].to_str().unwrap(),
+        "application/geo+json"
+    );
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["type"], "FeatureCollection");
+
+    let features = data["features"].as_array().unwrap();
+    assert_eq!(features.len(), 6);
+
+    let bradford = &features[1];
+    assert_eq!(bradford["type"], "Feature");
+    assert_eq!(bradford["geometry"]["type"], "Point");
+    assert_eq!(
+        bradford["geometry"]["coordinates"],
+        serde_json::json!([-87.877, 43.0626])
+    );
+    assert_eq!(bradford["properties"]["name"], "Bradford");
+    assert_eq!(bradford["properties"]["bouy_id"], serde_json::Value::Null);
+    assert_eq!(bradford["properties"]["station_id"], "MLWW3");
+}
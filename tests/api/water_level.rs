@@ -0,0 +1,39 @@
+use crate::{helpers::TestApp, mocked_happy_path_test_app};
+
+#[tokio::test]
+async fn it_returns_the_water_level_as_json() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!("http://{}/api/water-level?spot=atwater", &app.addr))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["station"], "9087057");
+    assert_eq!(data["level"], 579.575);
+    assert_eq!(data["trend"], "rising");
+    assert_eq!(data["seiche_alert"], false);
+    assert_eq!(data["units"]["height"], "ft");
+}
+
+#[tokio::test]
+async fn it_returns_the_water_level_in_metric() {
+    let app = mocked_happy_path_test_app!();
+
+    let response = reqwest::get(format!(
+        "http://{}/api/water-level?spot=atwater&units=metric",
+        &app.addr
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(response.status().as_u16(), 200);
+
+    let data = response.json::<serde_json::Value>().await.unwrap();
+
+    assert_eq!(data["level"], 176.654);
+    assert_eq!(data["units"]["height"], "m");
+}
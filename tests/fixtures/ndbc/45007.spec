@@ -0,0 +1,5 @@
+#YY  MM DD hh mm WVHT  SwH  SwP  WWH  WWP SwD WWD  STEEPNESS  APD MWD
+#yr  mo dy hr mn    m    m  sec    m  sec  -  degT     -      sec degT
+2024 06 10 21 40  0.7  0.5  8.3  0.4  4.5 NNW  NNE       AVERAGE  3.9  15
+2024 06 10 20 40  0.7  0.5  8.3  0.4  4.2 NNW  NNE       AVERAGE  3.8  14
+2024 06 10 19 40  0.6   MM   MM  0.6  4.2  MM  NNE           N/A  3.7  12
@@ -0,0 +1,6 @@
+#YY  MM DD hh mm WVHT  SwH  SwP  WWH  WWP SwD WWD  STEEPNESS  APD MWD
+#yr  mo dy hr mn    m    m  sec    m  sec  -  degT     -      sec degT
+2024 06 10 21 50  0.4  0.1  7.7  0.4  4.0   E  NNE    VERY_STEEP  3.3  MM
+2024 06 10 21 20  0.4  0.1  7.1  0.4  4.0   E  NNE    VERY_STEEP  3.2  42
+2024 06 10 20 50  0.3  0.1  6.7  0.3  3.8 ESE   NE         STEEP  3.1  38
+2024 06 10 20 20  0.3  0.1  6.7  0.3  3.6 ESE   NE         STEEP  3.1  36